@@ -0,0 +1,229 @@
+use jiff::{SignedDuration, civil::DateTime};
+
+use crate::{
+    event::{Event, ResurrectEvent, Target},
+    parser::ParsedEvent,
+    segment::{Segment, SegmentKind},
+};
+
+// Rebirth, Raise Ally, Soulstone and Intercession
+const BATTLE_RES_SPELLS: [u32; 4] = [20484, 61999, 95750, 391054];
+
+// Raids share 90 minutes of charges between the whole group
+const RAID_RECHARGE: SignedDuration = SignedDuration::from_mins(90);
+const CHALLENGE_MODE_RECHARGE: SignedDuration = SignedDuration::from_mins(10);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BattleRes {
+    pub timestamp: DateTime,
+    pub caster: Target,
+    pub target: Target,
+    pub spell_id: u32,
+    // Charges available just before this one was used
+    pub charges: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BattleResReport {
+    pub recharge: SignedDuration,
+    pub uses: Vec<BattleRes>,
+    // Charges left when the segment ended
+    pub remaining: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct BattleResTracker {
+    spells: Vec<u32>,
+}
+
+impl Default for BattleResTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BattleResTracker {
+    pub fn new() -> Self {
+        Self {
+            spells: BATTLE_RES_SPELLS.to_vec(),
+        }
+    }
+
+    pub fn with_spells(mut self, spells: impl IntoIterator<Item = u32>) -> Self {
+        self.spells.extend(spells);
+        self
+    }
+
+    pub fn track(&self, events: &[ParsedEvent<'_>], segment: &Segment) -> BattleResReport {
+        // Boss pulls inside a key share the charges of the whole run
+        let (start, recharge, used) = match enclosing_key(events, segment) {
+            Some(key) => (
+                events[key].timestamp,
+                CHALLENGE_MODE_RECHARGE,
+                self.uses(&events[key..segment.events.start]).count(),
+            ),
+            None => (segment.start, recharge_time(&segment.kind), 0),
+        };
+
+        let mut uses: Vec<BattleRes> = Vec::new();
+        for (event, res) in self.uses(segment.slice(events)) {
+            uses.push(BattleRes {
+                timestamp: event.timestamp,
+                caster: res.caster,
+                target: res.target,
+                spell_id: res.spell.spell_id,
+                charges: charges_at(start, event.timestamp, recharge, used + uses.len()),
+            });
+        }
+
+        BattleResReport {
+            recharge,
+            remaining: charges_at(start, segment.end, recharge, used + uses.len()),
+            uses,
+        }
+    }
+
+    fn uses<'e, 'a>(
+        &self,
+        events: &'e [ParsedEvent<'a>],
+    ) -> impl Iterator<Item = (&'e ParsedEvent<'a>, ResurrectEvent)> {
+        events.iter().filter_map(|event| {
+            let Event::Combat(combat) = &event.event else {
+                return None;
+            };
+
+            combat
+                .resurrect()
+                .filter(|res| self.spells.contains(&res.spell.spell_id))
+                .map(|res| (event, res))
+        })
+    }
+}
+
+// Index of the CHALLENGE_MODE_START for the key an encounter was pulled in
+fn enclosing_key(events: &[ParsedEvent<'_>], segment: &Segment) -> Option<usize> {
+    if !matches!(segment.kind, SegmentKind::Encounter { .. }) {
+        return None;
+    }
+
+    events[..segment.events.start]
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(i, event)| match event.event {
+            Event::ChallengeModeStart(_) => Some(Some(i)),
+            Event::ChallengeModeEnd(_) => Some(None),
+            _ => None,
+        })
+        .flatten()
+}
+
+fn recharge_time(kind: &SegmentKind) -> SignedDuration {
+    match kind {
+        SegmentKind::Encounter { group_size, .. } => RAID_RECHARGE / (*group_size).max(1) as i32,
+        SegmentKind::ChallengeMode { .. } => CHALLENGE_MODE_RECHARGE,
+        // Nothing comes back during a match
+        SegmentKind::Arena { .. } => SignedDuration::MAX,
+    }
+}
+
+// Every segment (or key) starts with a single charge
+fn charges_at(start: DateTime, now: DateTime, recharge: SignedDuration, used: usize) -> u32 {
+    let elapsed = now.duration_since(start).as_millis().max(0);
+    let gained = 1 + elapsed / recharge.as_millis().max(1);
+
+    u32::try_from(gained)
+        .unwrap_or(u32::MAX)
+        .saturating_sub(used as u32)
+}
+
+#[cfg(test)]
+mod battle_res_tests {
+    use super::*;
+    use crate::{
+        segment::segments,
+        testing::{parse, pull},
+    };
+
+    fn res(ts: &str, spell_id: u32) -> String {
+        format!(
+            "4/19/2026 {ts}  SPELL_RESURRECT,Player-1-0001,\"Druid-Realm\",0x514,0x0,Player-1-0002,\"Tank-Realm\",0x514,0x0,{spell_id},\"Rebirth\",0x8\n"
+        )
+    }
+
+    fn reports(log: &str) -> Vec<BattleResReport> {
+        let events = parse(log);
+        let tracker = BattleResTracker::new();
+        segments(&events)
+            .iter()
+            .map(|segment| tracker.track(&events, segment))
+            .collect()
+    }
+
+    #[test]
+    fn raid_charges_recharge_by_group_size() {
+        let log = pull(
+            "20:00:00.000",
+            "20:10:00.000",
+            &[
+                res("20:01:00.000", 20484),
+                // A mass resurrect after a wipe isn't a battle res
+                res("20:02:00.000", 212036),
+                res("20:04:40.000", 61999),
+                res("20:09:30.000", 20484),
+            ],
+        );
+
+        let reports = reports(&log);
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.recharge, SignedDuration::from_secs(270));
+
+        let charges = report.uses.iter().map(|r| r.charges).collect::<Vec<_>>();
+        assert_eq!(charges, vec![1, 1, 1]);
+        assert_eq!(report.remaining, 0);
+    }
+
+    #[test]
+    fn keys_recharge_every_ten_minutes() {
+        let log = [
+            "4/19/2026 20:00:00.000  CHALLENGE_MODE_START,\"The Rookery\",2648,499,10,[10,147,9]\n"
+                .to_string(),
+            res("20:12:00.000", 95750),
+            "4/19/2026 20:31:00.000  CHALLENGE_MODE_END,2648,1,10,1860000,2500.0,2520.0\n"
+                .to_string(),
+        ]
+        .concat();
+
+        let reports = reports(&log);
+        assert_eq!(reports[0].uses[0].charges, 2);
+        assert_eq!(reports[0].remaining, 3);
+    }
+
+    #[test]
+    fn boss_pulls_in_a_key_share_its_charges() {
+        let log = [
+            "4/19/2026 20:00:00.000  CHALLENGE_MODE_START,\"The Rookery\",2648,499,10,[10,147,9]\n"
+                .to_string(),
+            res("20:05:00.000", 20484),
+            "4/19/2026 20:12:00.000  ENCOUNTER_START,2816,\"Kyrioss\",8,5,2648\n".to_string(),
+            res("20:21:00.000", 20484),
+            "4/19/2026 20:25:00.000  ENCOUNTER_END,2816,\"Kyrioss\",8,5,1,780000\n".to_string(),
+            "4/19/2026 20:31:00.000  CHALLENGE_MODE_END,2648,1,10,1860000,2500.0,2520.0\n"
+                .to_string(),
+        ]
+        .concat();
+
+        let reports = reports(&log);
+        assert_eq!(reports.len(), 2);
+
+        let boss = &reports[1];
+        assert_eq!(boss.recharge, SignedDuration::from_mins(10));
+        assert_eq!(boss.uses[0].charges, 2);
+        assert_eq!(boss.remaining, 1);
+
+        let key = &reports[0];
+        assert_eq!(key.uses.len(), 2);
+        assert_eq!(key.remaining, 2);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use jiff::civil::DateTime;
+
+use crate::{
+    event::Guid,
+    parser::ParsedEvent,
+    player::Combatant,
+    segment::{SegmentKind, segments},
+};
+
+// The War Within flasks, hearty and regular Well Fed, and the augment runes
+// from the last two expansions
+const FLASKS: [u32; 6] = [431971, 431972, 431973, 431974, 432021, 432473];
+const FOOD: [u32; 3] = [461957, 461959, 462210];
+const AUGMENT_RUNES: [u32; 2] = [393438, 453250];
+
+// Arcane Intellect, Power Word: Fortitude, Battle Shout, Mark of the Wild,
+// Skyfury and Blessing of the Bronze
+const RAID_BUFFS: [u32; 6] = [1459, 21562, 6673, 1126, 462854, 381748];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Consumable {
+    Flask,
+    Food,
+    AugmentRune,
+    RaidBuff,
+}
+
+// An aura already up when the pull started
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct InitialAura {
+    pub caster: Guid,
+    pub spell_id: u32,
+    pub stacks: u32,
+    pub consumable: Option<Consumable>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrePull {
+    pub guid: Guid,
+    pub start: DateTime,
+    pub auras: Vec<InitialAura>,
+}
+
+impl PrePull {
+    pub fn has(&self, consumable: Consumable) -> bool {
+        self.auras
+            .iter()
+            .any(|aura| aura.consumable == Some(consumable))
+    }
+
+    // Personal consumables the player went in without. Raid buffs depend on
+    // who is in the group so they are left to `raid_buffs`.
+    pub fn missing(&self) -> Vec<Consumable> {
+        [Consumable::Flask, Consumable::Food, Consumable::AugmentRune]
+            .into_iter()
+            .filter(|consumable| !self.has(*consumable))
+            .collect()
+    }
+
+    pub fn raid_buffs(&self) -> impl Iterator<Item = u32> {
+        self.auras
+            .iter()
+            .filter(|aura| aura.consumable == Some(Consumable::RaidBuff))
+            .map(|aura| aura.spell_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullConsumables {
+    pub encounter_id: u32,
+    pub start: DateTime,
+    pub players: Vec<PrePull>,
+}
+
+impl PullConsumables {
+    // Players missing at least one personal consumable
+    pub fn unprepared(&self) -> impl Iterator<Item = (&PrePull, Vec<Consumable>)> {
+        self.players.iter().filter_map(|player| {
+            let missing = player.missing();
+            (!missing.is_empty()).then_some((player, missing))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsumableCheck {
+    spells: HashMap<u32, Consumable>,
+}
+
+impl Default for ConsumableCheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConsumableCheck {
+    pub fn new() -> Self {
+        Self {
+            spells: HashMap::new(),
+        }
+        .with_spells(Consumable::Flask, FLASKS)
+        .with_spells(Consumable::Food, FOOD)
+        .with_spells(Consumable::AugmentRune, AUGMENT_RUNES)
+        .with_spells(Consumable::RaidBuff, RAID_BUFFS)
+    }
+
+    // Replaces the consumable of any spell already in the table
+    pub fn with_spells(
+        mut self,
+        consumable: Consumable,
+        spells: impl IntoIterator<Item = u32>,
+    ) -> Self {
+        self.spells
+            .extend(spells.into_iter().map(|spell| (spell, consumable)));
+        self
+    }
+
+    pub fn snapshot(&self, combatant: &Combatant, start: DateTime) -> PrePull {
+        PrePull {
+            guid: combatant.guid,
+            start,
+            auras: combatant
+                .auras
+                .iter()
+                .map(|aura| InitialAura {
+                    caster: aura.caster,
+                    spell_id: aura.spell_id,
+                    stacks: aura.stacks,
+                    consumable: self.spells.get(&aura.spell_id).copied(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn pulls(&self, events: &[ParsedEvent<'_>]) -> Vec<PullConsumables> {
+        segments(events)
+            .into_iter()
+            .filter_map(|segment| {
+                let SegmentKind::Encounter { encounter_id, .. } = segment.kind else {
+                    return None;
+                };
+
+                Some(PullConsumables {
+                    encounter_id,
+                    start: segment.start,
+                    players: segment
+                        .combatants(events)
+                        .into_iter()
+                        .map(|combatant| self.snapshot(combatant, segment.start))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod consumables_tests {
+    use super::*;
+    use crate::testing::{CombatantLine, parse, pull};
+
+    fn combatant(guid: &str, auras: &str) -> String {
+        CombatantLine::new(guid, 62).with_auras(auras).line()
+    }
+
+    #[test]
+    fn pre_pull_auras_are_checked() {
+        let log = pull(
+            "20:00:00.000",
+            "20:05:00.000",
+            &[
+                combatant(
+                    "Player-1-0001",
+                    "Player-1-0001,432021,1,Player-1-0001,462210,1,Player-1-0001,453250,1,Player-1-0001,1459,1",
+                ),
+                combatant(
+                    "Player-1-0002",
+                    "Player-1-0001,1459,1,Player-1-0002,999999,3",
+                ),
+                combatant("Player-1-0003", ""),
+            ],
+        );
+        let events = parse(&log);
+        let pulls = ConsumableCheck::new().pulls(&events);
+        assert_eq!(pulls.len(), 1);
+
+        let pull = &pulls[0];
+        assert_eq!(pull.players.len(), 3);
+        assert!(pull.players[0].missing().is_empty());
+        assert_eq!(pull.players[0].raid_buffs().collect::<Vec<_>>(), vec![1459]);
+
+        let second = &pull.players[1];
+        assert_eq!(second.auras.len(), 2);
+        assert_eq!(second.auras[1].consumable, None);
+        assert_eq!(second.auras[1].stacks, 3);
+        assert_eq!(
+            second.missing(),
+            vec![Consumable::Flask, Consumable::Food, Consumable::AugmentRune]
+        );
+
+        assert!(pull.players[2].auras.is_empty());
+        assert_eq!(pull.unprepared().count(), 2);
+
+        let pulls = ConsumableCheck::new()
+            .with_spells(Consumable::Flask, [999999])
+            .pulls(&events);
+        assert!(pulls[0].players[1].has(Consumable::Flask));
+
+        let pulls = ConsumableCheck::new()
+            .with_spells(Consumable::RaidBuff, [432021])
+            .pulls(&events);
+        assert_eq!(pulls[0].players[0].missing(), vec![Consumable::Flask]);
+        assert_eq!(pulls[0].players[0].raid_buffs().count(), 2);
+    }
+}
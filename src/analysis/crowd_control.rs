@@ -0,0 +1,445 @@
+use std::{collections::HashMap, fmt::Display};
+
+use jiff::{SignedDuration, civil::DateTime};
+
+use crate::{
+    event::{CombatEvent, Event, Guid, SpellParameters, Suffix, Target},
+    parser::ParsedEvent,
+    segment::Segment,
+    types::EventType,
+};
+
+// A category is back to full duration this long after the last crowd control
+// from it wore off
+const DR_RESET: SignedDuration = SignedDuration::from_secs(18);
+
+// Debuff ids rather than the spell that was cast, e.g. Fear is 118699 and
+// not 5782
+const STUNS: [u32; 7] = [408, 1833, 853, 132169, 132168, 5211, 179057];
+const INCAPACITATES: [u32; 7] = [118, 6770, 1776, 51514, 20066, 99, 3355];
+const DISORIENTS: [u32; 6] = [118699, 2094, 8122, 33786, 5246, 31661];
+const SILENCES: [u32; 3] = [81261, 15487, 1330];
+const ROOTS: [u32; 3] = [122, 339, 64695];
+const DISARMS: [u32; 2] = [236077, 207777];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DrCategory {
+    Stun,
+    Incapacitate,
+    Disorient,
+    Silence,
+    Root,
+    Disarm,
+}
+
+impl Display for DrCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stun => write!(f, "Stun"),
+            Self::Incapacitate => write!(f, "Incapacitate"),
+            Self::Disorient => write!(f, "Disorient"),
+            Self::Silence => write!(f, "Silence"),
+            Self::Root => write!(f, "Root"),
+            Self::Disarm => write!(f, "Disarm"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DrLevel {
+    Full,
+    Half,
+    Quarter,
+    Immune,
+}
+
+impl DrLevel {
+    // The level for the nth crowd control of a category inside one window
+    fn nth(applied: usize) -> Self {
+        match applied {
+            0 => Self::Full,
+            1 => Self::Half,
+            2 => Self::Quarter,
+            _ => Self::Immune,
+        }
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            Self::Full => 1.0,
+            Self::Half => 0.5,
+            Self::Quarter => 0.25,
+            Self::Immune => 0.0,
+        }
+    }
+}
+
+impl Display for DrLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full => write!(f, "100%"),
+            Self::Half => write!(f, "50%"),
+            Self::Quarter => write!(f, "25%"),
+            Self::Immune => write!(f, "Immune"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Break {
+    pub timestamp: DateTime,
+    pub by: Option<Target>,
+    // `None` when it was broken by a melee swing
+    pub spell: Option<SpellParameters>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrowdControl {
+    pub caster: Option<Target>,
+    pub target: Target,
+    pub spell: SpellParameters,
+    pub category: DrCategory,
+    pub dr: DrLevel,
+    pub start: DateTime,
+    // Anything still up when the segment finished ends with it
+    pub end: DateTime,
+    pub broken: Option<Break>,
+}
+
+impl CrowdControl {
+    pub fn duration(&self) -> SignedDuration {
+        self.end.duration_since(self.start)
+    }
+}
+
+// Everything from one category on one target before its DR reset
+#[derive(Debug, Clone, PartialEq)]
+pub struct CcChain {
+    pub target: Guid,
+    pub category: DrCategory,
+    pub controls: Vec<CrowdControl>,
+}
+
+impl CcChain {
+    pub fn start(&self) -> Option<DateTime> {
+        self.controls.first().map(|cc| cc.start)
+    }
+
+    pub fn end(&self) -> Option<DateTime> {
+        self.controls.iter().map(|cc| cc.end).max()
+    }
+
+    // Time spent under at least one of the chain's crowd controls
+    pub fn duration(&self) -> SignedDuration {
+        let mut total = SignedDuration::ZERO;
+        let mut covered: Option<DateTime> = None;
+        for cc in &self.controls {
+            let start = covered.map_or(cc.start, |covered| covered.max(cc.start));
+            if cc.end > start {
+                total += cc.end.duration_since(start);
+                covered = Some(cc.end);
+            }
+        }
+
+        total
+    }
+
+    // Whether the next one from this category would be immune if it landed
+    // before the reset
+    pub fn is_immune(&self) -> bool {
+        self.controls
+            .last()
+            .is_some_and(|cc| cc.dr >= DrLevel::Quarter)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CcReport {
+    // In the order each one started
+    pub chains: Vec<CcChain>,
+}
+
+impl CcReport {
+    pub fn on(&self, target: Guid) -> impl Iterator<Item = &CcChain> {
+        self.chains
+            .iter()
+            .filter(move |chain| chain.target == target)
+    }
+
+    pub fn broken(&self) -> impl Iterator<Item = &CrowdControl> {
+        self.chains
+            .iter()
+            .flat_map(|chain| &chain.controls)
+            .filter(|cc| cc.broken.is_some())
+    }
+
+    // Total time each target spent crowd controlled
+    pub fn time_controlled(&self) -> Vec<(Guid, SignedDuration)> {
+        let mut totals: Vec<(Guid, SignedDuration)> = Vec::new();
+        for chain in &self.chains {
+            match totals.iter_mut().find(|(guid, _)| *guid == chain.target) {
+                Some((_, total)) => *total += chain.duration(),
+                None => totals.push((chain.target, chain.duration())),
+            }
+        }
+
+        totals
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CcTracker {
+    spells: HashMap<u32, DrCategory>,
+    reset: SignedDuration,
+}
+
+impl Default for CcTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CcTracker {
+    pub fn new() -> Self {
+        Self {
+            spells: HashMap::new(),
+            reset: DR_RESET,
+        }
+        .with_spells(DrCategory::Stun, STUNS)
+        .with_spells(DrCategory::Incapacitate, INCAPACITATES)
+        .with_spells(DrCategory::Disorient, DISORIENTS)
+        .with_spells(DrCategory::Silence, SILENCES)
+        .with_spells(DrCategory::Root, ROOTS)
+        .with_spells(DrCategory::Disarm, DISARMS)
+    }
+
+    // Replaces the category of any spell already in the table
+    pub fn with_spells(
+        mut self,
+        category: DrCategory,
+        spells: impl IntoIterator<Item = u32>,
+    ) -> Self {
+        self.spells
+            .extend(spells.into_iter().map(|spell| (spell, category)));
+        self
+    }
+
+    pub fn with_reset(mut self, reset: SignedDuration) -> Self {
+        self.reset = reset;
+        self
+    }
+
+    pub fn category(&self, spell_id: u32) -> Option<DrCategory> {
+        self.spells.get(&spell_id).copied()
+    }
+
+    pub fn track(&self, events: &[ParsedEvent<'_>], segment: &Segment) -> CcReport {
+        let mut chains: Vec<CcChain> = Vec::new();
+        let mut windows: HashMap<(Guid, DrCategory), Window> = HashMap::new();
+        // Index of the chain and the control within it for every aura still up
+        let mut open: HashMap<(Guid, u32), (usize, usize)> = HashMap::new();
+
+        for event in segment.slice(events) {
+            let Event::Combat(combat) = &event.event else {
+                continue;
+            };
+
+            let (Some(target), Some(spell)) = (combat.dst, combat.spell) else {
+                continue;
+            };
+
+            let Some(category) = self.category(spell.spell_id) else {
+                continue;
+            };
+
+            let key = (target.guid, spell.spell_id);
+            match event.event_type {
+                // A refresh is the same spell being cast again so it goes
+                // through DR like a fresh application
+                EventType::SpellAuraApplied | EventType::SpellAuraRefresh => {
+                    if let Some(index) = open.remove(&key) {
+                        self.close(&mut chains, &mut windows, index, event.timestamp);
+                    }
+
+                    let window = windows.entry((target.guid, category)).or_default();
+                    if window.expired(event.timestamp) {
+                        chains.push(CcChain {
+                            target: target.guid,
+                            category,
+                            controls: Vec::new(),
+                        });
+                        *window = Window {
+                            chain: chains.len() - 1,
+                            ..Window::default()
+                        };
+                    }
+
+                    let chain = &mut chains[window.chain];
+                    chain.controls.push(CrowdControl {
+                        caster: combat.src,
+                        target,
+                        spell,
+                        category,
+                        dr: DrLevel::nth(window.applied),
+                        start: event.timestamp,
+                        end: segment.end,
+                        broken: None,
+                    });
+                    window.applied += 1;
+                    window.active += 1;
+                    window.reset_at = None;
+                    open.insert(key, (window.chain, chain.controls.len() - 1));
+                }
+                EventType::SpellAuraRemoved => {
+                    if let Some(index) = open.remove(&key) {
+                        self.close(&mut chains, &mut windows, index, event.timestamp);
+                    }
+                }
+                EventType::SpellAuraBroken | EventType::SpellAuraBrokenSpell => {
+                    let Some(index) = open.remove(&key) else {
+                        continue;
+                    };
+
+                    chains[index.0].controls[index.1].broken = Some(Break {
+                        timestamp: event.timestamp,
+                        by: combat.src,
+                        spell: breaking_spell(combat),
+                    });
+                    self.close(&mut chains, &mut windows, index, event.timestamp);
+                }
+                _ => {}
+            }
+        }
+
+        CcReport { chains }
+    }
+
+    fn close(
+        &self,
+        chains: &mut [CcChain],
+        windows: &mut HashMap<(Guid, DrCategory), Window>,
+        (chain, control): (usize, usize),
+        timestamp: DateTime,
+    ) {
+        let chain = &mut chains[chain];
+        chain.controls[control].end = timestamp;
+
+        if let Some(window) = windows.get_mut(&(chain.target, chain.category)) {
+            window.active = window.active.saturating_sub(1);
+            if window.active == 0 {
+                window.reset_at = Some(timestamp + self.reset);
+            }
+        }
+    }
+}
+
+// DR state for one category on one target
+#[derive(Debug, Default)]
+struct Window {
+    chain: usize,
+    applied: usize,
+    active: usize,
+    // When DR wears off, which is `None` while any crowd control of the
+    // window is still up
+    reset_at: Option<DateTime>,
+}
+
+impl Window {
+    fn expired(&self, now: DateTime) -> bool {
+        match self.reset_at {
+            Some(reset_at) => self.active == 0 && now >= reset_at,
+            None => self.applied == 0,
+        }
+    }
+}
+
+fn breaking_spell(combat: &CombatEvent<'_>) -> Option<SpellParameters> {
+    match &combat.suffix {
+        Some(Suffix::AuraBrokenSpell(broken)) => Some(broken.spell),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod crowd_control_tests {
+    use super::*;
+    use crate::{segment::segments, testing::parse_with_symbols};
+
+    const ROGUE: &str = "Player-1-0001,\"Rogue-Realm\",0x511,0x0";
+    const MAGE: &str = "Player-1-0002,\"Mage-Realm\",0x511,0x0";
+    const ENEMY: &str = "Player-1-0003,\"Enemy-Realm\",0x548,0x0";
+    const HEALER: &str = "Player-1-0004,\"Healer-Realm\",0x548,0x0";
+
+    fn aura(ts: &str, event: &str, src: &str, spell: (u32, &str), extra: &str) -> String {
+        format!(
+            "4/19/2026 21:00:{ts}  {event},{src},{ENEMY},{},\"{}\",0x1,{extra}\n",
+            spell.0, spell.1
+        )
+    }
+
+    #[test]
+    fn crowd_control_chains_apply_dr() {
+        let kidney = (408, "Kidney Shot");
+        let cheap = (1833, "Cheap Shot");
+        let poly = (118, "Polymorph");
+        let log = [
+            "4/19/2026 21:00:00.000  ARENA_MATCH_START,1552,33,3v3,1\n".to_string(),
+            aura("01.000", "SPELL_AURA_APPLIED", ROGUE, cheap, "DEBUFF"),
+            aura("05.000", "SPELL_AURA_REMOVED", ROGUE, cheap, "DEBUFF"),
+            aura("06.000", "SPELL_AURA_APPLIED", ROGUE, kidney, "DEBUFF"),
+            aura("09.000", "SPELL_AURA_REMOVED", ROGUE, kidney, "DEBUFF"),
+            aura("10.000", "SPELL_AURA_APPLIED", MAGE, poly, "DEBUFF"),
+            format!(
+                "4/19/2026 21:00:12.500  SPELL_AURA_BROKEN_SPELL,{HEALER},{ENEMY},118,\"Polymorph\",0x40,589,\"Shadow Word: Pain\",0x20,DEBUFF\n"
+            ),
+            aura("12.600", "SPELL_AURA_REMOVED", MAGE, poly, "DEBUFF"),
+            aura("20.000", "SPELL_AURA_APPLIED", ROGUE, kidney, "DEBUFF"),
+            aura("20.500", "SPELL_AURA_REMOVED", ROGUE, kidney, "DEBUFF"),
+            aura("40.000", "SPELL_AURA_APPLIED", ROGUE, kidney, "DEBUFF"),
+            "4/19/2026 21:00:42.000  ARENA_MATCH_END,1,42,1800,1750\n".to_string(),
+        ]
+        .concat();
+
+        let (events, symbols) = parse_with_symbols(&log);
+        let segment = &segments(&events)[0];
+
+        let report = CcTracker::new().track(&events, segment);
+        assert_eq!(report.chains.len(), 3);
+
+        let stuns = &report.chains[0];
+        assert_eq!(stuns.category, DrCategory::Stun);
+        assert_eq!(
+            stuns.controls.iter().map(|cc| cc.dr).collect::<Vec<_>>(),
+            vec![DrLevel::Full, DrLevel::Half, DrLevel::Quarter]
+        );
+        assert!(stuns.is_immune());
+        assert_eq!(stuns.controls[1].duration(), SignedDuration::from_secs(3));
+        assert_eq!(stuns.duration(), SignedDuration::from_millis(7500));
+
+        let broken = report.broken().collect::<Vec<_>>();
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].category, DrCategory::Incapacitate);
+        assert_eq!(broken[0].duration(), SignedDuration::from_millis(2500));
+        let by = broken[0].broken.as_ref().expect("broken");
+        assert_eq!(by.by.map(|t| symbols.resolve(t.name)), Some("Healer-Realm"));
+        assert_eq!(by.spell.map(|s| s.spell_id), Some(589));
+
+        // The last stun came after the reset and ran until the match ended
+        let last = &report.chains[2];
+        assert_eq!(last.controls[0].dr, DrLevel::Full);
+        assert_eq!(last.controls[0].end, segment.end);
+
+        let target = stuns.target;
+        assert_eq!(report.on(target).count(), 3);
+        assert_eq!(
+            report.time_controlled(),
+            vec![(target, SignedDuration::from_millis(12000))]
+        );
+
+        let report = CcTracker::new()
+            .with_reset(SignedDuration::from_secs(60))
+            .track(&events, segment);
+        assert_eq!(report.chains.len(), 2);
+        assert_eq!(report.chains[0].controls[3].dr, DrLevel::Immune);
+    }
+}
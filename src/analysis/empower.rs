@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use jiff::{SignedDuration, civil::DateTime};
+
+use crate::{
+    event::{Event, EventType, Guid, Suffix},
+    parser::ParsedEvent,
+};
+
+// Fire Breath and Eternity Surge, which hit with a different spell id to
+// the one being held
+const EMPOWER_SPELLS: [(u32, u32); 2] = [(357208, 357209), (359073, 359077)];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmpowerCast {
+    pub caster: Guid,
+    pub spell_id: u32,
+    pub start: DateTime,
+    pub end: DateTime,
+    pub rank: u32,
+    pub interrupted: bool,
+    pub damage: u64,
+    pub healing: u64,
+}
+
+impl EmpowerCast {
+    pub fn hold_time(&self) -> SignedDuration {
+        self.end.duration_since(self.start)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankSummary {
+    pub rank: u32,
+    pub casts: usize,
+    pub damage: u64,
+    pub healing: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmpowerReport {
+    // In the order the empowers were released
+    pub casts: Vec<EmpowerCast>,
+    // Releases without a start, e.g. the log began mid-cast
+    pub orphaned: usize,
+}
+
+impl EmpowerReport {
+    pub fn interrupted(&self) -> impl Iterator<Item = &EmpowerCast> {
+        self.casts.iter().filter(|cast| cast.interrupted)
+    }
+
+    pub fn timeline(&self, caster: Guid) -> impl Iterator<Item = &EmpowerCast> {
+        self.casts.iter().filter(move |cast| cast.caster == caster)
+    }
+
+    // Completed casts of a spell grouped by the rank they were released at
+    pub fn by_rank(&self, spell_id: u32) -> Vec<RankSummary> {
+        let mut ranks: Vec<RankSummary> = Vec::new();
+        for cast in &self.casts {
+            if cast.spell_id != spell_id || cast.interrupted {
+                continue;
+            }
+
+            let index = match ranks.iter().position(|r| r.rank == cast.rank) {
+                Some(index) => index,
+                None => {
+                    ranks.push(RankSummary {
+                        rank: cast.rank,
+                        casts: 0,
+                        damage: 0,
+                        healing: 0,
+                    });
+                    ranks.len() - 1
+                }
+            };
+
+            ranks[index].casts += 1;
+            ranks[index].damage += cast.damage;
+            ranks[index].healing += cast.healing;
+        }
+
+        ranks.sort_by_key(|r| r.rank);
+        ranks
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EmpowerTracker {
+    // Hit spell id to the empowered spell it came from
+    spells: HashMap<u32, u32>,
+}
+
+impl Default for EmpowerTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EmpowerTracker {
+    pub fn new() -> Self {
+        Self {
+            spells: EMPOWER_SPELLS
+                .iter()
+                .map(|(empower, hit)| (*hit, *empower))
+                .collect(),
+        }
+    }
+
+    pub fn with_spell(mut self, empower: u32, hit: u32) -> Self {
+        self.spells.insert(hit, empower);
+        self
+    }
+
+    pub fn track(&self, events: &[ParsedEvent<'_>]) -> EmpowerReport {
+        let mut report = EmpowerReport::default();
+        let mut started: HashMap<(Guid, u32), DateTime> = HashMap::new();
+        // The last completed cast of each empower, which gets the credit for
+        // any hits (and damage over time) until it is cast again
+        let mut last: HashMap<(Guid, u32), usize> = HashMap::new();
+
+        for event in events {
+            let Event::Combat(combat) = &event.event else {
+                continue;
+            };
+
+            let (Some(src), Some(spell)) = (combat.src, combat.spell) else {
+                continue;
+            };
+
+            let key = (src.guid, spell.spell_id);
+            match (event.event_type, &combat.suffix) {
+                (EventType::SpellEmpowerStart, _) => {
+                    started.insert(key, event.timestamp);
+                }
+                (
+                    EventType::SpellEmpowerEnd | EventType::SpellEmpowerInterrupt,
+                    Some(Suffix::Empower(rank)),
+                ) => {
+                    let Some(start) = started.remove(&key) else {
+                        report.orphaned += 1;
+                        continue;
+                    };
+
+                    let interrupted = event.event_type == EventType::SpellEmpowerInterrupt;
+                    if !interrupted {
+                        last.insert(key, report.casts.len());
+                    }
+
+                    report.casts.push(EmpowerCast {
+                        caster: src.guid,
+                        spell_id: spell.spell_id,
+                        start,
+                        end: event.timestamp,
+                        rank: *rank,
+                        interrupted,
+                        damage: 0,
+                        healing: 0,
+                    });
+                }
+                (_, Some(Suffix::Damage(_) | Suffix::Heal(_))) => {
+                    let empower = self
+                        .spells
+                        .get(&spell.spell_id)
+                        .copied()
+                        .unwrap_or(spell.spell_id);
+                    let Some(index) = last.get(&(src.guid, empower)) else {
+                        continue;
+                    };
+
+                    let cast = &mut report.casts[*index];
+                    match &combat.suffix {
+                        Some(Suffix::Damage(damage)) => cast.damage += damage.amount as u64,
+                        Some(Suffix::Heal(heal)) => cast.healing += heal.amount as u64,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod empower_tests {
+    use super::*;
+    use crate::testing::parse;
+
+    const EVOKER: &str = "Player-1-0001,\"Evoker-Realm\",0x511,0x0";
+    const BOSS: &str = "Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0";
+    const ADV: &str = "Player-1-0001,0000000000000000,100,200,300,400,500,0,0,0,0,100,100,0,-10.5,20.25,2769,1.5,639";
+
+    fn empower(ts: &str, event: &str, suffix: &str) -> String {
+        format!(
+            "4/19/2026 {ts}  {event},{EVOKER},0000000000000000,nil,0x80000000,0x80000000,357208,\"Fire Breath\",0x4{suffix}\n"
+        )
+    }
+
+    fn hit(ts: &str, amount: u32) -> String {
+        format!(
+            "4/19/2026 {ts}  SPELL_DAMAGE,{EVOKER},{BOSS},357209,\"Fire Breath\",0x4,{ADV},{amount},{amount},-1,4,0,0,0,nil,nil,nil\n"
+        )
+    }
+
+    #[test]
+    fn it_pairs_empowers_and_credits_their_hits() {
+        let log = [
+            empower("20:00:00.000", "SPELL_EMPOWER_START", ""),
+            empower("20:00:01.500", "SPELL_EMPOWER_END", ",3"),
+            hit("20:00:01.600", 1000),
+            hit("20:00:03.600", 500),
+            empower("20:00:10.000", "SPELL_EMPOWER_START", ""),
+            empower("20:00:10.400", "SPELL_EMPOWER_INTERRUPT", ",1"),
+            hit("20:00:10.600", 200),
+            empower("20:00:20.000", "SPELL_EMPOWER_START", ""),
+            empower("20:00:20.500", "SPELL_EMPOWER_END", ",1"),
+            hit("20:00:20.600", 300),
+            empower("20:00:30.000", "SPELL_EMPOWER_END", ",4"),
+        ]
+        .concat();
+
+        let events = parse(&log);
+        let report = EmpowerTracker::new().track(&events);
+
+        assert_eq!(report.casts.len(), 3);
+        assert_eq!(report.orphaned, 1);
+        assert_eq!(report.interrupted().count(), 1);
+        assert_eq!(
+            report.casts[0].hold_time(),
+            SignedDuration::from_millis(1500)
+        );
+
+        let ranks = report
+            .by_rank(357208)
+            .into_iter()
+            .map(|r| (r.rank, r.casts, r.damage))
+            .collect::<Vec<_>>();
+        // The hit after the interrupted cast still belongs to the rank 3 DoT
+        assert_eq!(ranks, vec![(1, 1, 300), (3, 1, 1700)]);
+    }
+}
@@ -0,0 +1,370 @@
+use jiff::civil::DateTime;
+
+use crate::{
+    event::Guid,
+    parser::ParsedEvent,
+    player::{Combatant, Equipment},
+    segment::{SegmentKind, segments},
+    types::EquipmentSlot,
+};
+
+// Slots with an enchant as of The War Within
+const ENCHANT_SLOTS: [EquipmentSlot; 8] = [
+    EquipmentSlot::Chest,
+    EquipmentSlot::Legs,
+    EquipmentSlot::Feet,
+    EquipmentSlot::Wrist,
+    EquipmentSlot::Finger1,
+    EquipmentSlot::Finger2,
+    EquipmentSlot::Back,
+    EquipmentSlot::MainHand,
+];
+
+// Bonus ids that add a socket to an item
+const SOCKET_BONUSES: [u32; 6] = [4802, 6935, 7576, 10397, 10531, 11307];
+
+// The in-game average is over sixteen slots, with a two hander counting
+// twice and the shirt and tabard ignored. COMBATANT_INFO doesn't say which
+// weapons are two handed so their ids come from `with_two_handers`, and any
+// other main hand with nothing in the off hand leaves that slot empty.
+const ITEM_LEVEL_SLOTS: f64 = 16.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemSet {
+    pub name: String,
+    pub items: Vec<u32>,
+    // Pieces needed for each bonus, e.g. `[2, 4]`
+    pub bonuses: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetCount {
+    pub name: String,
+    pub equipped: u32,
+    pub active: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GearReport {
+    pub guid: Guid,
+    pub item_level: f64,
+    // Equipped items without an enchant
+    pub missing_enchants: Vec<EquipmentSlot>,
+    // Number of sockets without a gem in each slot that has any
+    pub empty_sockets: Vec<(EquipmentSlot, u32)>,
+    pub sets: Vec<SetCount>,
+}
+
+impl GearReport {
+    pub fn is_complete(&self) -> bool {
+        self.missing_enchants.is_empty() && self.empty_sockets.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullGear {
+    pub encounter_id: u32,
+    pub name: String,
+    pub start: DateTime,
+    // One per player in the order the client wrote them
+    pub reports: Vec<GearReport>,
+}
+
+impl PullGear {
+    pub fn average_item_level(&self) -> f64 {
+        if self.reports.is_empty() {
+            return 0.0;
+        }
+
+        self.reports.iter().map(|r| r.item_level).sum::<f64>() / self.reports.len() as f64
+    }
+
+    pub fn missing_enchants(&self) -> usize {
+        self.reports.iter().map(|r| r.missing_enchants.len()).sum()
+    }
+
+    pub fn empty_sockets(&self) -> u32 {
+        self.reports
+            .iter()
+            .flat_map(|r| r.empty_sockets.iter().map(|(_, count)| count))
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GearAuditor {
+    enchant_slots: Vec<EquipmentSlot>,
+    socket_bonuses: Vec<u32>,
+    sets: Vec<ItemSet>,
+    two_handers: Vec<u32>,
+}
+
+impl Default for GearAuditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GearAuditor {
+    pub fn new() -> Self {
+        Self {
+            enchant_slots: ENCHANT_SLOTS.to_vec(),
+            socket_bonuses: SOCKET_BONUSES.to_vec(),
+            sets: Vec::new(),
+            two_handers: Vec::new(),
+        }
+    }
+
+    pub fn with_enchant_slots(mut self, slots: impl IntoIterator<Item = EquipmentSlot>) -> Self {
+        self.enchant_slots = slots.into_iter().collect();
+        self
+    }
+
+    pub fn with_socket_bonuses(mut self, bonuses: impl IntoIterator<Item = u32>) -> Self {
+        self.socket_bonuses.extend(bonuses);
+        self
+    }
+
+    pub fn with_item_sets(mut self, sets: impl IntoIterator<Item = ItemSet>) -> Self {
+        self.sets.extend(sets);
+        self
+    }
+
+    pub fn with_two_handers(mut self, items: impl IntoIterator<Item = u32>) -> Self {
+        self.two_handers.extend(items);
+        self
+    }
+
+    pub fn audit(&self, combatant: &Combatant) -> GearReport {
+        let gear = combatant.gear();
+
+        let mut missing_enchants = Vec::new();
+        for slot in &self.enchant_slots {
+            if combatant
+                .item(*slot)
+                .is_some_and(|item| item.enchantment.is_none())
+            {
+                missing_enchants.push(*slot);
+            }
+        }
+
+        let mut empty_sockets = Vec::new();
+        for (slot, item) in gear.iter() {
+            let Some(item) = item else {
+                continue;
+            };
+
+            let sockets = self.sockets(item);
+            let filled = item.gems.len() as u32;
+            if sockets > filled {
+                empty_sockets.push((*slot, sockets - filled));
+            }
+        }
+
+        let sets = self
+            .sets
+            .iter()
+            .filter_map(|set| {
+                let equipped = gear
+                    .values()
+                    .flatten()
+                    .filter(|item| set.items.contains(&item.item_id))
+                    .count() as u32;
+                if equipped == 0 {
+                    return None;
+                }
+
+                Some(SetCount {
+                    name: set.name.clone(),
+                    equipped,
+                    active: set
+                        .bonuses
+                        .iter()
+                        .copied()
+                        .filter(|pieces| *pieces <= equipped)
+                        .collect(),
+                })
+            })
+            .collect();
+
+        GearReport {
+            guid: combatant.guid,
+            item_level: self.item_level(combatant),
+            missing_enchants,
+            empty_sockets,
+            sets,
+        }
+    }
+
+    // COMBATANT_INFO is written for everyone in the group just after each
+    // ENCOUNTER_START, so every pull gets its own snapshot
+    pub fn pulls(&self, events: &[ParsedEvent<'_>]) -> Vec<PullGear> {
+        segments(events)
+            .into_iter()
+            .filter_map(|segment| {
+                let SegmentKind::Encounter {
+                    encounter_id, name, ..
+                } = &segment.kind
+                else {
+                    return None;
+                };
+
+                Some(PullGear {
+                    encounter_id: *encounter_id,
+                    name: name.clone(),
+                    start: segment.start,
+                    reports: segment
+                        .combatants(events)
+                        .into_iter()
+                        .map(|combatant| self.audit(combatant))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    fn sockets(&self, item: &Equipment) -> u32 {
+        item.bonuses
+            .iter()
+            .filter(|bonus| self.socket_bonuses.contains(bonus))
+            .count() as u32
+    }
+
+    fn item_level(&self, combatant: &Combatant) -> f64 {
+        let mut total: u32 = combatant
+            .gear()
+            .into_iter()
+            .filter(|(slot, _)| !slot.is_cosmetic())
+            .filter_map(|(_, item)| item.map(|item| item.item_level))
+            .sum();
+
+        if combatant.item(EquipmentSlot::OffHand).is_none() {
+            total += combatant
+                .item(EquipmentSlot::MainHand)
+                .filter(|item| self.two_handers.contains(&item.item_id))
+                .map(|item| item.item_level)
+                .unwrap_or_default();
+        }
+
+        total as f64 / ITEM_LEVEL_SLOTS
+    }
+}
+
+#[cfg(test)]
+mod gear_tests {
+    use super::*;
+    use crate::{
+        event::Event,
+        testing::{CombatantLine, parse, pull},
+    };
+
+    fn item(id: u32, level: u32, enchant: &str, bonuses: &str, gems: &str) -> String {
+        format!("({id},{level},({enchant}),({bonuses}),({gems}))")
+    }
+
+    fn combatant(guid: &str, weapon_level: u32, enchanted: bool) -> String {
+        let enchant = if enchanted { "7534,0,0" } else { "" };
+        let gear = (0..18).map(|slot| match slot {
+            1 => item(100, 600, "", "4802,4802", "213746,600"),
+            3 | 16 | 17 => item(0, 0, "", "", ""),
+            4 => item(200, 600, enchant, "", ""),
+            6 => item(201, 600, "", "", ""),
+            10 => item(202, 600, "7340,0,0", "4802", ""),
+            15 => item(300, weapon_level, "", "", ""),
+            _ => item(400 + slot, 600, "7340,0,0", "", ""),
+        });
+
+        CombatantLine::new(guid, 253)
+            .with_gear(gear)
+            .with_auras("Player-1-0003,1459,1")
+            .line()
+    }
+
+    #[test]
+    fn gear_is_audited_per_pull() {
+        let log = pull(
+            "20:00:00.000",
+            "20:05:00.000",
+            &[
+                combatant("Player-1-0001", 616, true),
+                combatant("Player-1-0002", 600, false),
+            ],
+        );
+        let events = parse(&log);
+
+        let auditor = GearAuditor::new()
+            .with_item_sets([ItemSet {
+                name: "Tier".to_string(),
+                items: vec![200, 201, 402, 405, 409],
+                bonuses: vec![2, 4],
+            }])
+            .with_two_handers([300]);
+        let Event::Combatant(combatant) = &events[1].event else {
+            panic!("expected combatant info");
+        };
+        let gear = combatant.gear();
+        assert_eq!(gear.len(), 18);
+        assert_eq!(gear[&EquipmentSlot::OffHand], None);
+        assert_eq!(
+            gear[&EquipmentSlot::MainHand].map(|item| item.item_level),
+            Some(616)
+        );
+
+        let pulls = auditor.pulls(&events);
+        assert_eq!(pulls.len(), 1);
+
+        let pull = &pulls[0];
+        assert_eq!(pull.encounter_id, 3009);
+        assert_eq!(pull.reports.len(), 2);
+
+        // Fifteen items at 600 with the two handed weapon counting twice
+        let first = &pull.reports[0];
+        assert!((first.item_level - 602.0).abs() < f64::EPSILON);
+        assert_eq!(
+            first.missing_enchants,
+            vec![EquipmentSlot::Legs, EquipmentSlot::MainHand]
+        );
+        assert_eq!(
+            first.empty_sockets,
+            vec![(EquipmentSlot::Neck, 1), (EquipmentSlot::Finger1, 1)]
+        );
+        assert_eq!(
+            first.sets,
+            vec![SetCount {
+                name: "Tier".to_string(),
+                equipped: 5,
+                active: vec![2, 4],
+            }]
+        );
+
+        let second = &pull.reports[1];
+        assert!((second.item_level - 600.0).abs() < f64::EPSILON);
+        assert_eq!(
+            second.missing_enchants,
+            vec![
+                EquipmentSlot::Chest,
+                EquipmentSlot::Legs,
+                EquipmentSlot::MainHand
+            ]
+        );
+        assert!(!second.is_complete());
+
+        assert!((pull.average_item_level() - 601.0).abs() < f64::EPSILON);
+        assert_eq!(pull.missing_enchants(), 5);
+        assert_eq!(pull.empty_sockets(), 4);
+    }
+
+    #[test]
+    fn one_handers_leave_the_off_hand_empty() {
+        let log = pull(
+            "20:00:00.000",
+            "20:05:00.000",
+            &[combatant("Player-1-0001", 616, true)],
+        );
+        let events = parse(&log);
+
+        // Fourteen items at 600 and the weapon, over sixteen slots
+        let pulls = GearAuditor::new().pulls(&events);
+        assert!((pulls[0].reports[0].item_level - 563.5).abs() < f64::EPSILON);
+    }
+}
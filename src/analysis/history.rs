@@ -0,0 +1,246 @@
+use jiff::civil::DateTime;
+
+use crate::{
+    event::Guid,
+    parser::ParsedEvent,
+    player::{Combatant, Equipment, Stats},
+    segment::{SegmentKind, segments},
+    talents::{TalentDiff, diff},
+    types::{EquipmentSlot, Specialization},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullSnapshot {
+    pub encounter_id: u32,
+    pub start: DateTime,
+    pub combatant: Combatant,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GearChange {
+    pub slot: EquipmentSlot,
+    pub from: Option<Equipment>,
+    pub to: Option<Equipment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Spec {
+        from: Specialization,
+        to: Specialization,
+    },
+    Talents(TalentDiff),
+    Gear(Vec<GearChange>),
+    Stats {
+        from: Stats,
+        to: Stats,
+    },
+}
+
+// Everything that differs from the player's previous pull
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadoutChange {
+    pub encounter_id: u32,
+    pub start: DateTime,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerHistory {
+    pub guid: Guid,
+    // One per pull the player was in
+    pub snapshots: Vec<PullSnapshot>,
+    pub changes: Vec<LoadoutChange>,
+}
+
+impl PlayerHistory {
+    pub fn talent_swaps(&self) -> impl Iterator<Item = (&LoadoutChange, &TalentDiff)> {
+        self.changes.iter().flat_map(|change| {
+            change.changes.iter().filter_map(move |c| match c {
+                Change::Talents(diff) => Some((change, diff)),
+                _ => None,
+            })
+        })
+    }
+
+    pub fn gear_swaps(&self) -> impl Iterator<Item = (&LoadoutChange, &GearChange)> {
+        self.changes.iter().flat_map(|change| {
+            change
+                .changes
+                .iter()
+                .filter_map(|c| match c {
+                    Change::Gear(gear) => Some(gear),
+                    _ => None,
+                })
+                .flatten()
+                .map(move |gear| (change, gear))
+        })
+    }
+
+    // The snapshot in use for a pull starting at `start`, if they were there
+    pub fn at(&self, start: DateTime) -> Option<&PullSnapshot> {
+        self.snapshots.iter().find(|s| s.start == start)
+    }
+}
+
+// Follows every player through the COMBATANT_INFO written at each pull, in
+// the order they first show up
+pub fn history(events: &[ParsedEvent<'_>]) -> Vec<PlayerHistory> {
+    let mut players: Vec<PlayerHistory> = Vec::new();
+
+    for segment in segments(events) {
+        let SegmentKind::Encounter { encounter_id, .. } = segment.kind else {
+            continue;
+        };
+
+        for combatant in segment.combatants(events) {
+            let snapshot = PullSnapshot {
+                encounter_id,
+                start: segment.start,
+                combatant: combatant.clone(),
+            };
+
+            let Some(player) = players.iter_mut().find(|p| p.guid == combatant.guid) else {
+                players.push(PlayerHistory {
+                    guid: combatant.guid,
+                    snapshots: vec![snapshot],
+                    changes: Vec::new(),
+                });
+                continue;
+            };
+
+            if let Some(previous) = player.snapshots.last() {
+                let changes = changes(&previous.combatant, combatant);
+                if !changes.is_empty() {
+                    player.changes.push(LoadoutChange {
+                        encounter_id,
+                        start: segment.start,
+                        changes,
+                    });
+                }
+            }
+
+            player.snapshots.push(snapshot);
+        }
+    }
+
+    players
+}
+
+fn changes(from: &Combatant, to: &Combatant) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    if from.spec != to.spec {
+        changes.push(Change::Spec {
+            from: from.spec,
+            to: to.spec,
+        });
+    }
+
+    let talents = diff(&from.talents, &to.talents);
+    if !talents.is_empty() {
+        changes.push(Change::Talents(talents));
+    }
+
+    let gear = EquipmentSlot::ALL
+        .into_iter()
+        .filter_map(|slot| {
+            let (old, new) = (from.item(slot), to.item(slot));
+            let same = match (old, new) {
+                (Some(old), Some(new)) => {
+                    old.item_id == new.item_id
+                        && old.item_level == new.item_level
+                        && old.enchantment == new.enchantment
+                        && old.gems == new.gems
+                }
+                (None, None) => true,
+                _ => false,
+            };
+
+            (!same).then(|| GearChange {
+                slot,
+                from: old.cloned(),
+                to: new.cloned(),
+            })
+        })
+        .collect::<Vec<_>>();
+    if !gear.is_empty() {
+        changes.push(Change::Gear(gear));
+    }
+
+    if from.stats != to.stats {
+        changes.push(Change::Stats {
+            from: from.stats,
+            to: to.stats,
+        });
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod history_tests {
+    use super::*;
+    use crate::{
+        player::Talent,
+        testing::{CombatantLine, parse, pull},
+    };
+
+    // A pull with just the one player in it
+    fn loadout(ts: &str, end: &str, talent: &str, enchant: &str, strength: u32) -> String {
+        let gear = (0..18).map(|slot| match slot {
+            4 => format!("(200,600,({enchant}),(),())"),
+            3 | 16 | 17 => "(0,0,(),(),())".to_string(),
+            _ => format!("({},600,(),(),())", 300 + slot),
+        });
+        let combatant = CombatantLine::new("Player-1-0001", 71)
+            .with_timestamp(ts)
+            .with_strength(strength)
+            .with_talents(&format!("(1,10,1),{talent}"))
+            .with_gear(gear)
+            .line();
+
+        pull(ts, end, &[combatant])
+    }
+
+    #[test]
+    fn loadout_changes_are_tracked_between_pulls() {
+        let log = [
+            loadout("20:00:00.000", "20:01:00.000", "(2,20,1)", "7364,0,0", 1000),
+            loadout("20:05:00.000", "20:06:00.000", "(2,20,1)", "7364,0,0", 1000),
+            loadout("20:10:00.000", "20:11:00.000", "(2,21,1)", "7365,0,0", 1100),
+        ]
+        .concat();
+
+        let events = parse(&log);
+        let players = history(&events);
+        assert_eq!(players.len(), 1);
+
+        let player = &players[0];
+        assert_eq!(player.snapshots.len(), 3);
+        assert_eq!(player.changes.len(), 1);
+
+        let change = &player.changes[0];
+        assert_eq!(change.start, player.snapshots[2].start);
+        assert_eq!(change.changes.len(), 3);
+
+        let swaps = player.talent_swaps().collect::<Vec<_>>();
+        assert_eq!(
+            swaps[0].1.changed,
+            vec![(Talent::new(2, 20, 1), Talent::new(2, 21, 1))]
+        );
+
+        let gear = player.gear_swaps().collect::<Vec<_>>();
+        assert_eq!(gear.len(), 1);
+        assert_eq!(gear[0].1.slot, EquipmentSlot::Chest);
+        assert_eq!(
+            gear[0].1.to.as_ref().and_then(|item| item.enchantment),
+            Some((7365, 0, 0))
+        );
+
+        assert!(matches!(
+            change.changes[2],
+            Change::Stats { from, to } if from.strength() == 1000 && to.strength() == 1100
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use jiff::{SignedDuration, civil::DateTime};
+
+use crate::{
+    event::{Event, Guid, PartyKillEvent, Suffix, Target},
+    parser::ParsedEvent,
+};
+
+// UNIT_DIED is written straight after the PARTY_KILL for the same unit
+const DEATH_WINDOW: SignedDuration = SignedDuration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kill {
+    pub timestamp: DateTime,
+    pub victim: Target,
+    // `None` when nobody in the group landed the killing blow
+    pub killer: Option<Target>,
+    // The killer, or its owner when the killing blow came from a summon
+    pub credited: Option<Guid>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KillerSummary {
+    pub killer: Guid,
+    pub npcs: usize,
+    pub players: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KillReport {
+    pub kills: Vec<Kill>,
+    // Killing blows with no matching death, e.g. units that go unconscious
+    // rather than dying
+    pub unconfirmed: usize,
+}
+
+impl KillReport {
+    pub fn npc_kills(&self) -> impl Iterator<Item = &Kill> {
+        self.kills
+            .iter()
+            .filter(|kill| kill.victim.unit_flags.is_npc())
+    }
+
+    pub fn player_kills(&self) -> impl Iterator<Item = &Kill> {
+        self.kills
+            .iter()
+            .filter(|kill| kill.victim.unit_flags.is_player())
+    }
+
+    // Most killing blows first
+    pub fn by_killer(&self) -> Vec<KillerSummary> {
+        let mut summaries: Vec<KillerSummary> = Vec::new();
+        for kill in &self.kills {
+            let Some(killer) = kill.credited else {
+                continue;
+            };
+
+            let index = match summaries.iter().position(|s| s.killer == killer) {
+                Some(index) => index,
+                None => {
+                    summaries.push(KillerSummary {
+                        killer,
+                        npcs: 0,
+                        players: 0,
+                    });
+                    summaries.len() - 1
+                }
+            };
+
+            if kill.victim.unit_flags.is_player() {
+                summaries[index].players += 1;
+            } else if kill.victim.unit_flags.is_npc() {
+                summaries[index].npcs += 1;
+            }
+        }
+
+        summaries.sort_by_key(|s| std::cmp::Reverse(s.npcs + s.players));
+        summaries
+    }
+}
+
+pub fn kills(events: &[ParsedEvent<'_>]) -> KillReport {
+    let mut report = KillReport::default();
+    let mut owners: HashMap<Guid, Guid> = HashMap::new();
+    let mut pending: Vec<(DateTime, PartyKillEvent)> = Vec::new();
+
+    for event in events {
+        let Event::Combat(combat) = &event.event else {
+            continue;
+        };
+
+        let horizon = event.timestamp.saturating_sub(DEATH_WINDOW);
+        let before = pending.len();
+        pending.retain(|(ts, _)| *ts >= horizon);
+        report.unconfirmed += before - pending.len();
+
+        if let Some(summon) = combat.summon() {
+            owners.insert(summon.pet.guid, summon.owner.guid);
+            continue;
+        }
+
+        if let Some(kill) = combat.party_kill() {
+            pending.push((event.timestamp, kill));
+            continue;
+        }
+
+        let (Some(Suffix::UnitDied(_)), Some(victim)) = (&combat.suffix, combat.dst) else {
+            continue;
+        };
+
+        let killer = pending
+            .iter()
+            .position(|(_, kill)| kill.victim.guid == victim.guid)
+            .map(|index| pending.remove(index).1.killer);
+
+        report.kills.push(Kill {
+            timestamp: event.timestamp,
+            victim,
+            killer,
+            credited: killer.map(|killer| *owners.get(&killer.guid).unwrap_or(&killer.guid)),
+        });
+    }
+
+    report.unconfirmed += pending.len();
+    report
+}
+
+#[cfg(test)]
+mod kills_tests {
+    use super::*;
+    use crate::testing::parse_with_symbols;
+
+    const LOG: &str = "4/19/2026 20:00:00.000  SPELL_SUMMON,Player-1-0001,\"Hunter-Realm\",0x511,0x0,Creature-0-1-2-3-100-1,\"Wolf\",0x1111,0x0,883,\"Call Pet 1\",0x1\n\
+        4/19/2026 20:00:10.000  PARTY_KILL,Player-1-0002,\"Mage-Realm\",0x512,0x0,Creature-0-1-2-3-200-1,\"Trash\",0xa48,0x0,0\n\
+        4/19/2026 20:00:10.000  UNIT_DIED,0000000000000000,nil,0x80000000,0x80000000,Creature-0-1-2-3-200-1,\"Trash\",0xa48,0x0,0\n\
+        4/19/2026 20:00:11.000  PARTY_KILL,Creature-0-1-2-3-100-1,\"Wolf\",0x1111,0x0,Creature-0-1-2-3-200-2,\"Trash\",0xa48,0x0,0\n\
+        4/19/2026 20:00:11.100  UNIT_DIED,0000000000000000,nil,0x80000000,0x80000000,Creature-0-1-2-3-200-2,\"Trash\",0xa48,0x0,0\n\
+        4/19/2026 20:00:12.000  UNIT_DIED,0000000000000000,nil,0x80000000,0x80000000,Creature-0-1-2-3-200-3,\"Trash\",0xa48,0x0,0\n\
+        4/19/2026 20:00:13.000  PARTY_KILL,Player-1-0002,\"Mage-Realm\",0x512,0x0,Creature-0-1-2-3-200-4,\"Trash\",0xa48,0x0,1\n\
+        4/19/2026 20:00:20.000  PARTY_KILL,Player-1-0001,\"Hunter-Realm\",0x511,0x0,Player-2-0003,\"Rogue-Realm\",0x548,0x0,0\n\
+        4/19/2026 20:00:20.000  UNIT_DIED,0000000000000000,nil,0x80000000,0x80000000,Player-2-0003,\"Rogue-Realm\",0x548,0x0,0\n";
+
+    #[test]
+    fn it_pairs_killing_blows_with_deaths() {
+        let (events, symbols) = parse_with_symbols(LOG);
+        let report = kills(&events);
+
+        assert_eq!(report.kills.len(), 4);
+        assert_eq!(report.unconfirmed, 1);
+        assert_eq!(report.npc_kills().count(), 3);
+        assert_eq!(report.player_kills().count(), 1);
+        assert_eq!(report.kills[2].killer, None);
+
+        // The wolf's killing blow is credited to the hunter
+        let killer = report.kills[1].killer.expect("a killer");
+        assert_eq!(symbols.resolve(killer.name), "Wolf");
+        let credited = report.kills[1].credited.expect("an owner");
+        assert_eq!(symbols.resolve(credited), "Player-1-0001");
+
+        let summary = report
+            .by_killer()
+            .into_iter()
+            .map(|s| (symbols.resolve(s.killer).to_string(), s.npcs, s.players))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("Player-1-0001".to_string(), 1, 1),
+                ("Player-1-0002".to_string(), 1, 0),
+            ]
+        );
+    }
+}
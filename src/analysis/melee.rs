@@ -0,0 +1,258 @@
+use jiff::{SignedDuration, civil::DateTime};
+
+use crate::{
+    event::{Event, EventType, Guid, MissType, Suffix, Target},
+    parser::ParsedEvent,
+};
+
+// SWING_DAMAGE and SWING_DAMAGE_LANDED are written for the same swing so only
+// one of them is ever counted
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum SwingSource {
+    #[default]
+    Damage,
+    Landed,
+}
+
+impl SwingSource {
+    fn event_type(&self) -> EventType {
+        match self {
+            Self::Damage => EventType::SwingDamage,
+            Self::Landed => EventType::SwingDamageLanded,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SwingOutcome {
+    Hit {
+        amount: u32,
+        critical: bool,
+        glancing: bool,
+        crushing: bool,
+    },
+    Miss(MissType),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Swing {
+    pub timestamp: DateTime,
+    pub target: Target,
+    pub offhand: bool,
+    pub outcome: SwingOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeleeReport {
+    pub attacker: Guid,
+    pub swings: Vec<Swing>,
+}
+
+impl MeleeReport {
+    // Time between consecutive swings of one hand, which is the effective
+    // swing timer once haste and any parry resets are applied
+    pub fn intervals(&self, offhand: bool) -> Vec<SignedDuration> {
+        let swings = self
+            .swings
+            .iter()
+            .filter(|swing| swing.offhand == offhand)
+            .collect::<Vec<_>>();
+
+        swings
+            .windows(2)
+            .map(|pair| pair[1].timestamp.duration_since(pair[0].timestamp))
+            .collect()
+    }
+
+    pub fn average_interval(&self, offhand: bool) -> Option<SignedDuration> {
+        let intervals = self.intervals(offhand);
+        let count = i32::try_from(intervals.len()).ok().filter(|n| *n > 0)?;
+
+        Some(intervals.into_iter().sum::<SignedDuration>() / count)
+    }
+
+    pub fn hits(&self) -> impl Iterator<Item = &Swing> {
+        self.swings
+            .iter()
+            .filter(|swing| matches!(swing.outcome, SwingOutcome::Hit { .. }))
+    }
+
+    pub fn damage(&self, offhand: bool) -> u64 {
+        self.swings
+            .iter()
+            .filter(|swing| swing.offhand == offhand)
+            .map(|swing| match swing.outcome {
+                SwingOutcome::Hit { amount, .. } => amount as u64,
+                SwingOutcome::Miss(_) => 0,
+            })
+            .sum()
+    }
+
+    pub fn critical_rate(&self) -> f64 {
+        self.rate(|critical, _, _| critical)
+    }
+
+    pub fn glancing_rate(&self) -> f64 {
+        self.rate(|_, glancing, _| glancing)
+    }
+
+    pub fn crushing_rate(&self) -> f64 {
+        self.rate(|_, _, crushing| crushing)
+    }
+
+    // Share of all swings, misses included
+    fn rate(&self, flag: impl Fn(bool, bool, bool) -> bool) -> f64 {
+        if self.swings.is_empty() {
+            return 0.0;
+        }
+
+        let count = self
+            .swings
+            .iter()
+            .filter(|swing| match swing.outcome {
+                SwingOutcome::Hit {
+                    critical,
+                    glancing,
+                    crushing,
+                    ..
+                } => flag(critical, glancing, crushing),
+                SwingOutcome::Miss(_) => false,
+            })
+            .count();
+
+        count as f64 / self.swings.len() as f64
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MeleeAnalyzer {
+    source: SwingSource,
+}
+
+impl MeleeAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn prefer_landed(mut self) -> Self {
+        self.source = SwingSource::Landed;
+        self
+    }
+
+    // One report per attacker in the order they first swing
+    pub fn analyze(&self, events: &[ParsedEvent<'_>]) -> Vec<MeleeReport> {
+        let hits = self.source.event_type();
+        let mut reports: Vec<MeleeReport> = Vec::new();
+
+        for event in events {
+            let Event::Combat(combat) = &event.event else {
+                continue;
+            };
+
+            let (Some(src), Some(dst)) = (combat.src, combat.dst) else {
+                continue;
+            };
+
+            let (offhand, outcome) = match (&combat.suffix, event.event_type) {
+                (Some(Suffix::Damage(damage)), event_type) if event_type == hits => (
+                    damage.is_offhand,
+                    SwingOutcome::Hit {
+                        amount: damage.amount,
+                        critical: damage.critical,
+                        glancing: damage.glancing,
+                        crushing: damage.crushing,
+                    },
+                ),
+                (Some(Suffix::Missed(miss)), EventType::SwingMissed) => {
+                    (miss.is_offhand, SwingOutcome::Miss(miss.miss_type))
+                }
+                _ => continue,
+            };
+
+            let swing = Swing {
+                timestamp: event.timestamp,
+                target: dst,
+                offhand,
+                outcome,
+            };
+
+            match reports.iter_mut().find(|r| r.attacker == src.guid) {
+                Some(report) => report.swings.push(swing),
+                None => reports.push(MeleeReport {
+                    attacker: src.guid,
+                    swings: vec![swing],
+                }),
+            }
+        }
+
+        reports
+    }
+}
+
+#[cfg(test)]
+mod melee_tests {
+    use super::*;
+    use crate::testing::parse;
+
+    const ROGUE: &str = "Player-1-0001,\"Rogue-Realm\",0x511,0x0";
+    const BOSS: &str = "Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0";
+    const ADV: &str = "Player-1-0001,0000000000000000,100,200,300,400,500,0,0,0,3,100,100,0,-10.5,20.25,2769,1.5,639";
+
+    fn swing(ts: &str, event: &str, amount: u32, flags: &str) -> String {
+        format!(
+            "4/19/2026 {ts}  {event},{ROGUE},{BOSS},{ADV},{amount},{amount},-1,1,0,0,0,{flags}\n"
+        )
+    }
+
+    fn miss(ts: &str, offhand: &str) -> String {
+        format!("4/19/2026 {ts}  SWING_MISSED,{ROGUE},{BOSS},DODGE,{offhand}\n")
+    }
+
+    fn log() -> String {
+        [
+            swing("20:00:00.000", "SWING_DAMAGE", 1000, "1,nil,nil,nil"),
+            swing("20:00:00.000", "SWING_DAMAGE_LANDED", 1000, "1,nil,nil,nil"),
+            swing("20:00:00.100", "SWING_DAMAGE", 500, "nil,1,nil,1"),
+            swing("20:00:00.100", "SWING_DAMAGE_LANDED", 500, "nil,1,nil,1"),
+            miss("20:00:02.600", "nil"),
+            swing("20:00:02.700", "SWING_DAMAGE", 500, "nil,nil,nil,1"),
+            swing("20:00:02.700", "SWING_DAMAGE_LANDED", 500, "nil,nil,nil,1"),
+            swing("20:00:05.200", "SWING_DAMAGE", 1000, "nil,nil,nil,nil"),
+            swing(
+                "20:00:05.200",
+                "SWING_DAMAGE_LANDED",
+                1000,
+                "nil,nil,nil,nil",
+            ),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn it_times_each_hand_without_double_counting() {
+        let log = log();
+        let events = parse(&log);
+
+        for analyzer in [MeleeAnalyzer::new(), MeleeAnalyzer::new().prefer_landed()] {
+            let reports = analyzer.analyze(&events);
+            assert_eq!(reports.len(), 1);
+
+            let report = &reports[0];
+            assert_eq!(report.swings.len(), 5);
+            assert_eq!(report.hits().count(), 4);
+            assert_eq!(report.damage(false), 2000);
+            assert_eq!(report.damage(true), 1000);
+            assert_eq!(
+                report.average_interval(false),
+                Some(SignedDuration::from_millis(2600))
+            );
+            assert_eq!(
+                report.intervals(true),
+                vec![SignedDuration::from_millis(2600)]
+            );
+            assert!((report.critical_rate() - 0.2).abs() < f64::EPSILON);
+            assert!((report.glancing_rate() - 0.2).abs() < f64::EPSILON);
+            assert_eq!(report.crushing_rate(), 0.0);
+        }
+    }
+}
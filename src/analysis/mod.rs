@@ -0,0 +1,11 @@
+pub mod battle_res;
+pub mod consumables;
+pub mod crowd_control;
+pub mod empower;
+pub mod gear;
+pub mod history;
+pub mod kills;
+pub mod melee;
+pub mod schools;
+pub mod stagger;
+pub mod support;
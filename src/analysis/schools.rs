@@ -0,0 +1,128 @@
+use crate::{
+    event::{Event, Guid, SpellSchool, Suffix},
+    parser::ParsedEvent,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchoolDamage {
+    pub school: SpellSchool,
+    pub amount: u64,
+}
+
+// Damage done per base school. Multi-school hits are split evenly between the
+// schools they are made of with any remainder going to the lowest one, so the
+// totals always add up to the damage in the log.
+pub fn damage_by_school(events: &[ParsedEvent<'_>], source: Option<Guid>) -> Vec<SchoolDamage> {
+    let mut totals: Vec<SchoolDamage> = Vec::new();
+
+    for event in events {
+        // Support events are a share of a hit that is already counted
+        if event.event_type.is_support() || event.event_type.is_duplicate() {
+            continue;
+        }
+
+        let Event::Combat(combat) = &event.event else {
+            continue;
+        };
+
+        let Some(Suffix::Damage(damage)) = &combat.suffix else {
+            continue;
+        };
+
+        if source.is_some_and(|source| combat.src.map(|src| src.guid) != Some(source)) {
+            continue;
+        }
+
+        for (school, amount) in split(damage.school, damage.amount as u64) {
+            match totals.iter_mut().find(|total| total.school == school) {
+                Some(total) => total.amount += amount,
+                None => totals.push(SchoolDamage { school, amount }),
+            }
+        }
+    }
+
+    totals.sort_by_key(|total| total.school.bits());
+    totals
+}
+
+fn split(school: SpellSchool, amount: u64) -> Vec<(SpellSchool, u64)> {
+    let schools = school.schools().collect::<Vec<_>>();
+    if schools.is_empty() {
+        // Nothing we recognise, keep it whole under the raw value
+        return vec![(school, amount)];
+    }
+
+    let share = amount / schools.len() as u64;
+    let remainder = amount % schools.len() as u64;
+    schools
+        .into_iter()
+        .enumerate()
+        .map(|(i, school)| (school, share + if i == 0 { remainder } else { 0 }))
+        .collect()
+}
+
+#[cfg(test)]
+mod schools_tests {
+    use super::*;
+    use crate::testing::parse;
+
+    #[test]
+    fn schools_are_bitflags() {
+        let frostfire = SpellSchool::from(20);
+        assert_eq!(frostfire, SpellSchool::FIRE | SpellSchool::FROST);
+        assert!(frostfire.contains(SpellSchool::FIRE));
+        assert!(!frostfire.contains(SpellSchool::ARCANE));
+        assert!(frostfire.is_multi_school());
+        assert_eq!(frostfire.to_string(), "Frostfire");
+
+        // Not a named combination, but still parsed and kept exactly
+        let odd = SpellSchool::from(0x1 | 0x8 | 0x40 | 0x80);
+        assert_eq!(odd.bits(), 0xc9);
+        assert_eq!(odd.name(), None);
+        assert_eq!(odd.to_string(), "Physical/Nature/Arcane/0x80");
+        assert_eq!(odd.schools().count(), 3);
+    }
+
+    #[test]
+    fn multi_school_damage_is_split() {
+        let hit = |school: &str, amount: u32| {
+            format!(
+                "4/19/2026 20:00:01.000  SPELL_DAMAGE,Player-1-0001,\"Mage-Realm\",0x512,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,44614,\"Flurry\",{school},Creature-0-1-2-3-4-5,0000000000000000,100,200,0,0,500,0,0,0,0,100,100,0,-10.5,20.25,2769,1.5,80,{amount},{amount},-1,{},0,0,0,nil,nil,nil,nil\n",
+                u8::from_str_radix(school.trim_start_matches("0x"), 16).expect("hex school")
+            )
+        };
+        let log = [hit("0x14", 1001), hit("0x10", 500)].concat();
+        let events = parse(&log);
+
+        let totals = damage_by_school(&events, None)
+            .into_iter()
+            .map(|total| (total.school, total.amount))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            totals,
+            vec![(SpellSchool::FIRE, 501), (SpellSchool::FROST, 1000)]
+        );
+    }
+
+    #[test]
+    fn support_and_landed_events_are_not_counted_again() {
+        const ADV: &str = "Creature-0-1-2-3-4-5,0000000000000000,100,200,0,0,500,0,0,0,0,100,100,0,-10.5,20.25,2769,1.5,80";
+        let log = format!(
+            "4/19/2026 20:00:01.000  SPELL_DAMAGE,Player-1-0001,\"Mage-Realm\",0x512,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,133,\"Fireball\",0x4,{ADV},10000,10000,-1,4,0,0,0,nil,nil,nil,nil\n\
+            4/19/2026 20:00:01.000  SPELL_DAMAGE_SUPPORT,Player-1-0001,\"Mage-Realm\",0x512,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,395152,\"Ebon Might\",0xc,{ADV},1500,1500,-1,4,0,0,0,nil,nil,nil,nil,Player-1-0002\n\
+            4/19/2026 20:00:02.000  SWING_DAMAGE,Player-1-0003,\"Warrior-Realm\",0x512,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,{ADV},3000,3000,-1,1,0,0,0,nil,nil,nil,nil\n\
+            4/19/2026 20:00:02.000  SWING_DAMAGE_LANDED,Player-1-0003,\"Warrior-Realm\",0x512,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,{ADV},3000,3000,-1,1,0,0,0,nil,nil,nil,nil\n"
+        );
+        let events = parse(&log);
+        assert_eq!(events.len(), 4);
+
+        let totals = damage_by_school(&events, None)
+            .into_iter()
+            .map(|total| (total.school, total.amount))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            totals,
+            vec![(SpellSchool::PHYSICAL, 3000), (SpellSchool::FIRE, 10000)]
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use jiff::{SignedDuration, civil::DateTime};
+
+use crate::{
+    event::{Event, EventType, Guid, MissType, Suffix},
+    parser::ParsedEvent,
+};
+
+// The damage over time debuff that drains the pool
+const STAGGER_SPELL: u32 = 124255;
+const PURIFYING_BREW: u32 = 119582;
+
+// How far apart a Purifying Brew cast and its STAGGER_CLEAR can be written
+const PURIFY_WINDOW: SignedDuration = SignedDuration::from_millis(50);
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StaggerSample {
+    pub timestamp: DateTime,
+    pub pool: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaggerReport {
+    pub guid: Guid,
+    // Damage moved into the pool
+    pub staggered: f64,
+    // Damage taken (or absorbed) from the pool ticking
+    pub taken: f64,
+    // Damage removed from the pool by Purifying Brew
+    pub purified: f64,
+    pub purifies: usize,
+    // Damage dropped from the pool any other way, e.g. dying or leaving combat
+    pub cleared: f64,
+    pub clears: usize,
+    pub samples: Vec<StaggerSample>,
+}
+
+impl StaggerReport {
+    fn new(guid: Guid) -> Self {
+        Self {
+            guid,
+            staggered: 0.0,
+            taken: 0.0,
+            purified: 0.0,
+            purifies: 0,
+            cleared: 0.0,
+            clears: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    // Share of everything staggered that was purified away
+    pub fn efficiency(&self) -> f64 {
+        if self.staggered > 0.0 {
+            self.purified / self.staggered
+        } else {
+            0.0
+        }
+    }
+
+    pub fn average_purify(&self) -> f64 {
+        if self.purifies > 0 {
+            self.purified / self.purifies as f64
+        } else {
+            0.0
+        }
+    }
+
+    pub fn peak(&self) -> Option<StaggerSample> {
+        self.samples
+            .iter()
+            .copied()
+            .max_by(|a, b| a.pool.total_cmp(&b.pool))
+    }
+
+    pub fn pool(&self) -> f64 {
+        self.samples.last().map(|s| s.pool).unwrap_or_default()
+    }
+
+    fn record(&mut self, timestamp: DateTime, change: f64) {
+        let pool = (self.pool() + change).max(0.0);
+        self.samples.push(StaggerSample { timestamp, pool });
+    }
+}
+
+// Rebuilds the stagger pool of every Brewmaster in the log, in the order they
+// first staggered anything
+pub fn stagger(events: &[ParsedEvent<'_>]) -> Vec<StaggerReport> {
+    let mut reports: Vec<StaggerReport> = Vec::new();
+    let mut purifies = purifies(events);
+
+    for event in events {
+        let (guid, change) = match &event.event {
+            Event::Stagger(stagger) => match event.event_type {
+                EventType::StaggerPrevented => (stagger.guid, stagger.amount as f64),
+                _ => (stagger.guid, -(stagger.amount as f64)),
+            },
+            Event::Combat(combat) => {
+                let (Some(spell), Some(dst)) = (combat.spell, combat.dst) else {
+                    continue;
+                };
+
+                if spell.spell_id != STAGGER_SPELL {
+                    continue;
+                }
+
+                let taken = match &combat.suffix {
+                    Some(Suffix::Damage(damage)) => {
+                        damage.amount as f64 + damage.absorbed.max(0) as f64
+                    }
+                    Some(Suffix::Missed(miss)) if miss.miss_type == MissType::Absorb => {
+                        miss.amount.unwrap_or_default().max(0) as f64
+                    }
+                    _ => continue,
+                };
+
+                (dst.guid, -taken)
+            }
+            _ => continue,
+        };
+
+        let index = match reports.iter().position(|r| r.guid == guid) {
+            Some(index) => index,
+            None => {
+                reports.push(StaggerReport::new(guid));
+                reports.len() - 1
+            }
+        };
+
+        let purify = (event.event_type == EventType::StaggerClear)
+            .then(|| {
+                purifies.iter().position(|(caster, cast)| {
+                    *caster == guid && event.timestamp.duration_since(*cast).abs() <= PURIFY_WINDOW
+                })
+            })
+            .flatten();
+
+        let report = &mut reports[index];
+        match event.event_type {
+            EventType::StaggerPrevented => report.staggered += change,
+            EventType::StaggerClear if let Some(purify) = purify => {
+                // Each cast only accounts for the one clear
+                purifies.swap_remove(purify);
+                report.purified -= change;
+                report.purifies += 1;
+            }
+            EventType::StaggerClear => {
+                report.cleared -= change;
+                report.clears += 1;
+            }
+            _ => report.taken -= change,
+        }
+
+        report.record(event.timestamp, change);
+    }
+
+    reports
+}
+
+// STAGGER_CLEAR doesn't say what caused it so purifies are told apart by the
+// Purifying Brew cast written alongside them
+fn purifies(events: &[ParsedEvent<'_>]) -> Vec<(Guid, DateTime)> {
+    events
+        .iter()
+        .filter(|event| event.event_type == EventType::SpellCastSuccess)
+        .filter_map(|event| match &event.event {
+            Event::Combat(combat) => combat
+                .spell
+                .filter(|spell| spell.spell_id == PURIFYING_BREW)
+                .and(combat.src)
+                .map(|src| (src.guid, event.timestamp)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod stagger_tests {
+    use super::*;
+    use crate::testing::parse;
+
+    fn tick(ts: &str, amount: u32) -> String {
+        format!(
+            "4/19/2026 {ts}  SPELL_PERIODIC_DAMAGE,Player-1-0001,\"Brew-Realm\",0x511,0x0,Player-1-0001,\"Brew-Realm\",0x511,0x0,124255,\"Stagger\",0x1,Player-1-0001,0000000000000000,100,200,300,400,500,0,0,0,0,100,100,0,-10.5,20.25,2769,1.5,639,{amount},{amount},-1,1,0,0,0,nil,nil,nil\n"
+        )
+    }
+
+    #[test]
+    fn it_rebuilds_the_pool() {
+        let log = [
+            "4/19/2026 20:00:00.000  STAGGER_PREVENTED,Player-1-0001,1234,10000.0\n".to_string(),
+            tick("20:00:00.500", 1000),
+            "4/19/2026 20:00:01.000  STAGGER_PREVENTED,Player-1-0001,1234,6000.0\n".to_string(),
+            "4/19/2026 20:00:01.500  SPELL_CAST_SUCCESS,Player-1-0001,\"Brew-Realm\",0x511,0x0,0000000000000000,nil,0x80000000,0x80000000,119582,\"Purifying Brew\",0x1,Player-1-0001,0000000000000000,100,200,300,400,500,0,0,0,3,100,100,0,-10.5,20.25,2769,1.5,639\n".to_string(),
+            "4/19/2026 20:00:01.500  STAGGER_CLEAR,Player-1-0001,7500.0\n".to_string(),
+            tick("20:00:02.000", 500),
+            "4/19/2026 20:00:03.000  STAGGER_PREVENTED,Player-1-0001,1234,4000.0\n".to_string(),
+            // Dropped on death
+            "4/19/2026 20:00:04.000  STAGGER_CLEAR,Player-1-0001,11000.0\n".to_string(),
+        ]
+        .concat();
+
+        let events = parse(&log);
+        let reports = stagger(&events);
+        assert_eq!(reports.len(), 1);
+
+        let report = &reports[0];
+        assert_eq!(report.staggered, 20000.0);
+        assert_eq!(report.taken, 1500.0);
+        assert_eq!(report.purified, 7500.0);
+        assert_eq!(report.purifies, 1);
+        assert_eq!(report.cleared, 11000.0);
+        assert_eq!(report.clears, 1);
+        assert_eq!(report.pool(), 0.0);
+        assert_eq!(report.peak().map(|s| s.pool), Some(15000.0));
+        assert!((report.efficiency() - 0.375).abs() < f64::EPSILON);
+        assert!((report.average_purify() - 7500.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn purifies_match_casts_a_few_ms_apart() {
+        let cast = |ts: &str| {
+            format!(
+                "4/19/2026 {ts}  SPELL_CAST_SUCCESS,Player-1-0001,\"Brew-Realm\",0x511,0x0,0000000000000000,nil,0x80000000,0x80000000,119582,\"Purifying Brew\",0x1,Player-1-0001,0000000000000000,100,200,300,400,500,0,0,0,3,100,100,0,-10.5,20.25,2769,1.5,639\n"
+            )
+        };
+        let log = [
+            "4/19/2026 20:00:00.000  STAGGER_PREVENTED,Player-1-0001,1234,10000.0\n".to_string(),
+            "4/19/2026 20:00:01.480  STAGGER_CLEAR,Player-1-0001,5000.0\n".to_string(),
+            cast("20:00:01.500"),
+            cast("20:00:03.000"),
+            // Too late to be from the cast
+            "4/19/2026 20:00:03.070  STAGGER_CLEAR,Player-1-0001,2000.0\n".to_string(),
+            cast("20:00:05.000"),
+            "4/19/2026 20:00:05.030  STAGGER_CLEAR,Player-1-0001,1500.0\n".to_string(),
+        ]
+        .concat();
+
+        let events = parse(&log);
+        let report = &stagger(&events)[0];
+        assert_eq!(report.purified, 6500.0);
+        assert_eq!(report.purifies, 2);
+        assert_eq!(report.cleared, 2000.0);
+        assert_eq!(report.clears, 1);
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use crate::{
+    event::{Event, Guid, Suffix},
+    parser::ParsedEvent,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Attribution {
+    // As the log records it, everything stays with the player who dealt it
+    // and `_SUPPORT` events are only a breakdown of that amount
+    #[default]
+    Recorded,
+    // Moves the supported share of each hit from the buffed player onto the
+    // Augmentation Evoker the way external tools rank them
+    Supporter,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contribution {
+    pub guid: Guid,
+    pub damage: u64,
+    pub healing: u64,
+    // Damage and healing this unit gained from being supported (when
+    // recorded) or moved onto it from others (when attributed)
+    pub supported: u64,
+}
+
+// Running totals are signed as a support event can be seen before the hit it
+// is taken from
+#[derive(Default)]
+struct Totals {
+    damage: i64,
+    healing: i64,
+    supported: u64,
+}
+
+fn entry<'t>(
+    totals: &'t mut HashMap<Guid, Totals>,
+    order: &mut Vec<Guid>,
+    guid: Guid,
+) -> &'t mut Totals {
+    totals.entry(guid).or_insert_with(|| {
+        order.push(guid);
+        Totals::default()
+    })
+}
+
+// Damage and effective healing per source in the order they first appear
+pub fn contributions(events: &[ParsedEvent<'_>], attribution: Attribution) -> Vec<Contribution> {
+    let mut order: Vec<Guid> = Vec::new();
+    let mut totals: HashMap<Guid, Totals> = HashMap::new();
+
+    for event in events {
+        if event.event_type.is_duplicate() {
+            continue;
+        }
+
+        let Event::Combat(combat) = &event.event else {
+            continue;
+        };
+
+        let Some(src) = combat.src else {
+            continue;
+        };
+
+        // Absorbs are written from the attacker's side so they are credited to
+        // whoever put the shield up
+        let (source, damage, healing) = match &combat.suffix {
+            Some(Suffix::Damage(damage)) => (src.guid, damage.amount as i64, 0),
+            Some(Suffix::Heal(heal)) => (
+                src.guid,
+                0,
+                heal.amount.saturating_sub(heal.overhealing) as i64,
+            ),
+            Some(Suffix::Absorbed(absorb)) => (absorb.caster.guid, 0, absorb.amount.max(0) as i64),
+            _ => continue,
+        };
+
+        match (combat.supporter(), attribution) {
+            (None, _) => {
+                let source = entry(&mut totals, &mut order, source);
+                source.damage += damage;
+                source.healing += healing;
+            }
+            (Some(_), Attribution::Recorded) => {
+                entry(&mut totals, &mut order, source).supported += (damage + healing) as u64;
+            }
+            (Some(supporter), Attribution::Supporter) => {
+                let source = entry(&mut totals, &mut order, source);
+                source.damage -= damage;
+                source.healing -= healing;
+
+                let supporter = entry(&mut totals, &mut order, supporter);
+                supporter.damage += damage;
+                supporter.healing += healing;
+                supporter.supported += (damage + healing) as u64;
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .map(|guid| {
+            let total = &totals[&guid];
+            Contribution {
+                guid,
+                damage: total.damage.max(0) as u64,
+                healing: total.healing.max(0) as u64,
+                supported: total.supported,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod support_tests {
+    use super::*;
+    use crate::testing::parse;
+
+    const ADV: &str = "Creature-0-1-2-3-4-5,0000000000000000,100,200,0,0,500,0,0,0,0,100,100,0,-10.5,20.25,2769,1.5,80";
+    const LOG: &str = "4/19/2026 20:00:01.000  SPELL_DAMAGE,Player-1-0001,\"Mage-Realm\",0x512,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,133,\"Fireball\",0x4,{ADV},10000,10000,-1,4,0,0,0,1,nil,nil,nil\n\
+        4/19/2026 20:00:01.000  SPELL_DAMAGE_SUPPORT,Player-1-0001,\"Mage-Realm\",0x512,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,395152,\"Ebon Might\",0xc,{ADV},1500,1500,-1,4,0,0,0,1,nil,nil,nil,Player-1-0002\n\
+        4/19/2026 20:00:02.000  SPELL_DAMAGE,Player-1-0002,\"Evoker-Realm\",0x512,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,395160,\"Eruption\",0x4,{ADV},4000,4000,-1,4,0,0,0,nil,nil,nil,nil\n";
+
+    fn totals(contributions: &[Contribution]) -> Vec<(u64, u64)> {
+        contributions
+            .iter()
+            .map(|c| (c.damage, c.supported))
+            .collect()
+    }
+
+    #[test]
+    fn support_damage_moves_to_the_evoker() {
+        let log = LOG.replace("{ADV}", ADV);
+        let events = parse(&log);
+
+        let Event::Combat(support) = &events[1].event else {
+            panic!("expected a combat event");
+        };
+        assert!(support.supporter().is_some());
+        assert!(matches!(
+            &support.suffix,
+            Some(Suffix::Damage(damage)) if damage.amount == 1500 && damage.critical
+        ));
+
+        let recorded = contributions(&events, Attribution::Recorded);
+        assert_eq!(totals(&recorded), vec![(10000, 1500), (4000, 0)]);
+
+        let moved = contributions(&events, Attribution::Supporter);
+        assert_eq!(totals(&moved), vec![(8500, 0), (5500, 1500)]);
+    }
+
+    #[test]
+    fn landed_swings_are_counted_once() {
+        let swing = |event: &str| {
+            format!(
+                "4/19/2026 20:00:01.000  {event},Player-1-0001,\"Warrior-Realm\",0x512,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,{ADV},3000,3000,-1,1,0,0,0,nil,nil,nil,nil\n"
+            )
+        };
+        let log = [swing("SWING_DAMAGE"), swing("SWING_DAMAGE_LANDED")].concat();
+        let events = parse(&log);
+        assert_eq!(events.len(), 2);
+
+        let recorded = contributions(&events, Attribution::Recorded);
+        assert_eq!(totals(&recorded), vec![(3000, 0)]);
+    }
+
+    #[test]
+    fn supported_absorbs_move_to_the_evoker() {
+        let absorb = |event: &str, amount: u32, supporter: &str| {
+            format!(
+                "4/19/2026 20:00:03.000  {event},Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,Player-1-0001,\"Mage-Realm\",0x512,0x0,1234,\"Smash\",0x1,Player-1-0003,\"Priest-Realm\",0x512,0x0,17,\"Power Word: Shield\",0x2,{amount},5000,nil{supporter}\n"
+            )
+        };
+        let log = [
+            absorb("SPELL_ABSORBED", 2000, ""),
+            absorb("SPELL_ABSORBED_SUPPORT", 300, ",Player-1-0002"),
+        ]
+        .concat();
+        let events = parse(&log);
+
+        let healing = |contributions: &[Contribution]| {
+            contributions
+                .iter()
+                .map(|c| (c.healing, c.supported))
+                .collect::<Vec<_>>()
+        };
+
+        let recorded = contributions(&events, Attribution::Recorded);
+        assert_eq!(healing(&recorded), vec![(2000, 300)]);
+
+        let moved = contributions(&events, Attribution::Supporter);
+        assert_eq!(healing(&moved), vec![(1700, 0), (300, 300)]);
+    }
+}
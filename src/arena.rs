@@ -0,0 +1,221 @@
+use jiff::SignedDuration;
+
+use crate::{
+    analysis::{
+        crowd_control::{CcReport, CcTracker},
+        kills::{KillReport, kills},
+        support::{Attribution, Contribution, contributions},
+    },
+    event::{Event, Guid},
+    parser::ParsedEvent,
+    player::{PvpStats, PvpTalents},
+    segment::{Segment, SegmentKind, segments},
+    types::{Faction, Specialization},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaPlayer {
+    pub guid: Guid,
+    pub spec: Specialization,
+    pub pvp_talents: PvpTalents,
+    pub pvp_stats: PvpStats,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaTeam {
+    pub id: u32,
+    pub players: Vec<ArenaPlayer>,
+    // Written by ARENA_MATCH_END, so `None` for matches that never finished
+    pub rating: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaMatch {
+    pub segment: Segment,
+    pub instance_id: u32,
+    pub match_type: String,
+    pub recorder_team: u32,
+    // Team 0 then team 1
+    pub teams: [ArenaTeam; 2],
+    pub winner: Option<u32>,
+    pub duration: SignedDuration,
+    // Against the recording team's rating after its previous match of the
+    // same type in this log
+    pub rating_change: Option<i64>,
+}
+
+impl ArenaMatch {
+    pub fn team(&self, id: u32) -> Option<&ArenaTeam> {
+        self.teams.iter().find(|team| team.id == id)
+    }
+
+    pub fn team_of(&self, guid: Guid) -> Option<u32> {
+        self.teams
+            .iter()
+            .find(|team| team.players.iter().any(|p| p.guid == guid))
+            .map(|team| team.id)
+    }
+
+    pub fn won(&self) -> Option<bool> {
+        self.segment.success
+    }
+
+    pub fn slice<'e, 'a>(&self, events: &'e [ParsedEvent<'a>]) -> &'e [ParsedEvent<'a>] {
+        self.segment.slice(events)
+    }
+
+    // Damage and healing done by each player in the match
+    pub fn contributions(&self, events: &[ParsedEvent<'_>]) -> Vec<Contribution> {
+        contributions(self.slice(events), Attribution::Recorded)
+            .into_iter()
+            .filter(|c| self.team_of(c.guid).is_some())
+            .collect()
+    }
+
+    pub fn kills(&self, events: &[ParsedEvent<'_>]) -> KillReport {
+        kills(self.slice(events))
+    }
+
+    pub fn crowd_control(&self, events: &[ParsedEvent<'_>], tracker: &CcTracker) -> CcReport {
+        tracker.track(events, &self.segment)
+    }
+}
+
+pub fn arena_matches(events: &[ParsedEvent<'_>]) -> Vec<ArenaMatch> {
+    let mut matches: Vec<ArenaMatch> = Vec::new();
+
+    for segment in segments(events) {
+        let SegmentKind::Arena {
+            instance_id,
+            match_type,
+            team_id,
+        } = &segment.kind
+        else {
+            continue;
+        };
+
+        let mut teams = [0, 1].map(|id| ArenaTeam {
+            id,
+            players: Vec::new(),
+            rating: None,
+        });
+        let mut winner = None;
+        let mut duration = segment.duration();
+
+        for combatant in segment.combatants(events) {
+            teams[team_index(combatant.faction)]
+                .players
+                .push(ArenaPlayer {
+                    guid: combatant.guid,
+                    spec: combatant.spec,
+                    pvp_talents: combatant.pvp_talents,
+                    pvp_stats: combatant.pvp_stats,
+                });
+        }
+
+        for event in segment.slice(events) {
+            if let Event::ArenaEnd(end) = &event.event {
+                // Anything but a team id, such as a draw, has no winner
+                winner = matches!(end.winning_team, 0 | 1).then_some(end.winning_team);
+                duration = SignedDuration::from_secs(end.match_duration as i64);
+                teams[0].rating = Some(end.new_rating_team_one);
+                teams[1].rating = Some(end.new_rating_team_two);
+            }
+        }
+
+        let rating = teams.get(*team_id as usize).and_then(|team| team.rating);
+        let previous = matches
+            .iter()
+            .rev()
+            .filter(|m| m.match_type == *match_type)
+            .find_map(|m| m.team(m.recorder_team).and_then(|team| team.rating));
+        let rating_change = rating
+            .zip(previous)
+            .map(|(rating, previous)| rating as i64 - previous as i64);
+
+        matches.push(ArenaMatch {
+            instance_id: *instance_id,
+            match_type: match_type.clone(),
+            recorder_team: *team_id,
+            teams,
+            winner,
+            duration,
+            rating_change,
+            segment,
+        });
+    }
+
+    matches
+}
+
+// In arenas the faction field of COMBATANT_INFO holds the team instead,
+// which reads as Horde for team 0 and Alliance for team 1
+fn team_index(faction: Faction) -> usize {
+    match faction {
+        Faction::Horde => 0,
+        Faction::Alliance => 1,
+    }
+}
+
+#[cfg(test)]
+mod arena_tests {
+    use super::*;
+    use crate::testing::{CombatantLine, parse};
+
+    fn combatant(guid: &str, team: u32, spec: u16) -> String {
+        CombatantLine::new(guid, spec)
+            .with_timestamp("21:00:00.100")
+            .with_faction(team)
+            .with_pvp("5,6,7,0", "200,40,1800,3")
+            .line()
+    }
+
+    fn arena(start: &str, end: &str, winner: u32, ratings: (u32, u32)) -> String {
+        [
+            format!("4/19/2026 {start}  ARENA_MATCH_START,1552,33,3v3,1\n"),
+            combatant("Player-1-0001", 1, 62),
+            combatant("Player-1-0002", 0, 71),
+            format!(
+                "4/19/2026 {end}  ARENA_MATCH_END,{winner},95,{},{}\n",
+                ratings.0, ratings.1
+            ),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn arena_matches_are_split_into_teams() {
+        let log = [
+            arena("21:00:00.000", "21:01:40.000", 1, (1750, 1800)),
+            "4/19/2026 21:02:00.000  ENCOUNTER_START,3009,\"Vexie\",16,20,2769\n".to_string(),
+            arena("21:10:00.000", "21:12:00.000", 0, (1790, 1785)),
+            arena("21:20:00.000", "21:22:00.000", 255, (1790, 1785)),
+        ]
+        .concat();
+
+        let events = parse(&log);
+        let matches = arena_matches(&events);
+        assert_eq!(matches.len(), 3);
+
+        let first = &matches[0];
+        assert_eq!(first.match_type, "3v3");
+        assert_eq!(first.recorder_team, 1);
+        assert_eq!(first.won(), Some(true));
+        assert_eq!(first.winner, Some(1));
+        assert_eq!(first.duration, SignedDuration::from_secs(95));
+        assert_eq!(first.teams[1].players[0].spec, Specialization::ArcaneMage);
+        assert_eq!(first.teams[1].players[0].pvp_talents, (5, 6, 7, 0));
+        assert_eq!(first.teams[1].players[0].pvp_stats.rating(), 1800);
+        assert_eq!(first.teams[0].rating, Some(1750));
+        assert_eq!(first.team_of(first.teams[0].players[0].guid), Some(0));
+        assert_eq!(first.rating_change, None);
+
+        let second = &matches[1];
+        assert_eq!(second.won(), Some(false));
+        assert_eq!(second.rating_change, Some(-15));
+
+        let draw = &matches[2];
+        assert_eq!(draw.winner, None);
+        assert_eq!(draw.won(), Some(false));
+    }
+}
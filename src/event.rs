@@ -1,56 +1,71 @@
-use crate::types::CastType;
+use std::borrow::Cow;
+
+use crate::{
+    intern::Symbol,
+    types::{CastType, owned},
+};
 pub use crate::{
     player::Combatant,
     types::{
-        AuraType, Difficulty, EnvironmentalType, EventType, Guid, MissType, MultiValue, PowerType,
-        RaidFlag, SpellSchool, Target, UnitFlags,
+        AdvancedLayout, AuraType, BuildFlavour, Dialect, Difficulty, EnvironmentalType, EventType,
+        Guid, MissType, MultiValue, PowerType, RaidFlag, SpellSchool, Target, UnitFlags,
     },
 };
 
-#[derive(Debug, Clone)]
-pub enum Event {
-    LogVersion(LogVersionEvent),
-    Combat(CombatEvent),
+// Combat events make up the vast majority of a log so boxing them would
+// just trade the padding for an allocation per line
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<'a> {
+    LogVersion(LogVersionEvent<'a>),
+    Combat(CombatEvent<'a>),
     Stagger(StaggerEvent),
     Combatant(Combatant),
-    EncounterStart(EncounterStartEvent),
-    EncounterEnd(EncounterEndEvent),
-    ArenaStart(ArenaStartEvent),
+    EncounterStart(EncounterStartEvent<'a>),
+    EncounterEnd(EncounterEndEvent<'a>),
+    ChallengeModeStart(ChallengeModeStartEvent<'a>),
+    ChallengeModeEnd(ChallengeModeEndEvent),
+    ArenaStart(ArenaStartEvent<'a>),
     ArenaEnd(ArenaEndEvent),
     WorldMarkerPlaced(WorldMarkerPlacedEvent),
     WorldMarkerRemoved(RaidFlag),
-    ZoneChange(ZoneChangeEvent),
-    MapChange(MapChangeEvent),
-    Enchant(EnchantEvent),
+    ZoneChange(ZoneChangeEvent<'a>),
+    MapChange(MapChangeEvent<'a>),
+    Enchant(EnchantEvent<'a>),
     Emote(EmoteEvent),
+    // An event type this version of the parser doesn't know about
+    Unknown {
+        name: Cow<'a, str>,
+        raw_args: Cow<'a, str>,
+    },
     Placeholder,
 }
 
-#[derive(Debug, Clone)]
-pub struct LogVersionEvent {
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogVersionEvent<'a> {
     pub version: u32,
     pub advanced_log: bool,
-    pub build: String,
+    pub build: Cow<'a, str>,
 }
 
-#[derive(Debug, Clone)]
-pub struct CombatEvent {
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombatEvent<'a> {
     pub src: Option<Target>,
     pub dst: Option<Target>,
     pub spell: Option<SpellParameters>,
     pub adv: Option<AdvancedParameters>,
     pub environmental: Option<EnvironmentalType>,
-    pub suffix: Option<Suffix>,
+    pub suffix: Option<Suffix<'a>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct SpellParameters {
     pub spell_id: u32,
-    pub spell_name: String,
+    pub spell_name: Symbol,
     pub school: SpellSchool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AdvancedParameters {
     pub info: Guid,
     pub owner: Guid,
@@ -58,8 +73,9 @@ pub struct AdvancedParameters {
     pub max_hp: u32,
     pub attack_power: u32,
     pub spell_power: u32,
-    // TODO: Determine what the missing two events are
     pub armor: i32,
+    // Only present in `AdvancedLayout::Extended` logs
+    pub support_power: Option<u32>,
     pub absorb: u32,
 
     pub power_type: MultiValue<PowerType>,
@@ -71,16 +87,19 @@ pub struct AdvancedParameters {
     pub y: f32,
     pub map_id: u32,
     pub facing: f32,
+    // The raw value from the log. For players this is their item level on
+    // retail layouts which is also exposed through `item_level`.
     pub level: u32,
+    pub item_level: Option<u32>,
 }
 
-#[derive(Debug, Clone)]
-pub enum Suffix {
+#[derive(Debug, Clone, PartialEq)]
+pub enum Suffix<'a> {
     Damage(DamageEvent),
     Missed(MissEvent),
     Heal(HealEvent),
     HealAbsorbed(HealAbsorbEvent),
-    Fail(FailEvent),
+    Fail(FailEvent<'a>),
     Absorbed(AbsorbEvent),
     Energize(EnergizeEvent),
     Drain(DrainEvent),
@@ -94,16 +113,25 @@ pub enum Suffix {
     AuraBroken(AuraType),
     AuraBrokenSpell(AuraWithSpellEvent),
     Empower(u32),
-    Enchant(EnchantEvent),
+    Enchant(EnchantEvent<'a>),
     UnitDied(u32),
     UnitDestroyed(u32),
     UnitDissipates(u32),
+    PartyKill(u32),
+    // Summons, creates and resurrects carry nothing past the spell, what they
+    // mean comes from the source and destination. See the accessors on
+    // `CombatEvent`.
+    Summon,
+    Create,
+    Resurrect,
+    Instakill(u32),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DamageEvent {
     pub amount: u32,
-    pub base_amount: u32,
+    // Missing from Classic Era and other Classic logs
+    pub base_amount: Option<u32>,
     pub overkill: u32,
     pub school: SpellSchool,
     pub resisted: u32,
@@ -112,14 +140,16 @@ pub struct DamageEvent {
     pub critical: bool,
     pub glancing: bool,
     pub crushing: bool,
+    pub is_offhand: bool,
+    pub supporter: Option<Guid>,
 }
 
-#[derive(Debug, Clone)]
-pub struct FailEvent {
-    pub msg: String,
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailEvent<'a> {
+    pub msg: Cow<'a, str>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MissEvent {
     pub miss_type: MissType,
     pub is_offhand: bool,
@@ -129,16 +159,18 @@ pub struct MissEvent {
     pub cast_type: Option<CastType>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct HealEvent {
     pub amount: u32,
-    pub base_amount: u32,
+    // Missing from Classic Era and other Classic logs
+    pub base_amount: Option<u32>,
     pub overhealing: u32,
     pub absorbed: u32,
     pub critical: bool,
+    pub supporter: Option<Guid>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct HealAbsorbEvent {
     pub extra: Target,
     pub spell: SpellParameters,
@@ -146,7 +178,7 @@ pub struct HealAbsorbEvent {
     pub total_absorbed: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AbsorbEvent {
     pub src_spell: Option<SpellParameters>,
     pub caster: Target,
@@ -154,10 +186,10 @@ pub struct AbsorbEvent {
     pub amount: i32,
     pub total_amount: u32,
     pub critical: bool,
-    pub target: Option<Guid>,
+    pub supporter: Option<Guid>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EnergizeEvent {
     pub amount: f32,
     pub over_energize: f32,
@@ -165,7 +197,7 @@ pub struct EnergizeEvent {
     pub max: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DrainEvent {
     pub amount: u32,
     pub power: PowerType,
@@ -173,70 +205,89 @@ pub struct DrainEvent {
     pub max: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StealEvent(pub SpellParameters);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StealWithAuraEvent {
     pub spell: SpellParameters,
     pub aura: AuraType,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AuraEvent {
     pub aura: AuraType,
     pub amount: Option<u32>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AuraWithSpellEvent {
     pub spell: SpellParameters,
     pub aura: AuraType,
 }
 
-#[derive(Debug, Clone)]
-pub struct EnchantEvent {
-    pub name: String,
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnchantEvent<'a> {
+    pub name: Cow<'a, str>,
     pub item_id: u32,
-    pub item_name: String,
+    pub item_name: Cow<'a, str>,
 }
 
-#[derive(Debug, Clone)]
-pub struct EncounterStartEvent {
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncounterStartEvent<'a> {
     pub encounter_id: u32,
-    pub encounter_name: String,
+    pub encounter_name: Cow<'a, str>,
     pub difficulty: Difficulty,
     pub group_size: u32,
     pub instance_id: u32,
 }
 
-#[derive(Debug, Clone)]
-pub struct EncounterEndEvent {
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncounterEndEvent<'a> {
     pub encounter_id: u32,
-    pub encounter_name: String,
+    pub encounter_name: Cow<'a, str>,
     pub difficulty: Difficulty,
     pub group_size: u32,
     pub success: bool,
     pub fight_time: u32,
 }
 
-#[derive(Debug, Clone)]
-pub struct ArenaStartEvent {
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChallengeModeStartEvent<'a> {
+    pub zone_name: Cow<'a, str>,
+    pub instance_id: u32,
+    pub challenge_mode_id: u32,
+    pub keystone_level: u32,
+    pub affixes: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChallengeModeEndEvent {
+    pub instance_id: u32,
+    pub success: bool,
+    pub keystone_level: u32,
+    // Milliseconds
+    pub total_time: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaStartEvent<'a> {
     pub instance_id: u32,
     pub unk: u32,
-    pub match_type: String,
+    pub match_type: Cow<'a, str>,
     pub team_id: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ArenaEndEvent {
-    pub winning_team: bool,
+    // Kept as written so anything other than team 0 or 1 can be told apart
+    pub winning_team: u32,
     pub match_duration: u32,
     pub new_rating_team_one: u32,
     pub new_rating_team_two: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WorldMarkerPlacedEvent {
     pub instance_id: u32,
     pub marker: RaidFlag,
@@ -244,29 +295,312 @@ pub struct WorldMarkerPlacedEvent {
     pub y: f32,
 }
 
-#[derive(Debug, Clone)]
-pub struct MapChangeEvent {
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapChangeEvent<'a> {
     pub map_id: u32,
-    pub map_name: String,
+    pub map_name: Cow<'a, str>,
     pub x0: f32,
     pub x1: f32,
     pub y0: f32,
     pub y1: f32,
 }
 
-#[derive(Debug, Clone)]
-pub struct ZoneChangeEvent {
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneChangeEvent<'a> {
     pub instance_id: u32,
-    pub zone_name: String,
+    pub zone_name: Cow<'a, str>,
     pub difficulty: Difficulty,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EmoteEvent;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StaggerEvent {
     pub guid: Guid,
     pub spell_id: Option<u32>,
     pub amount: f32,
 }
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SummonEvent {
+    pub owner: Target,
+    pub pet: Target,
+    pub spell: SpellParameters,
+}
+
+// Portals, totems, feasts and the like. The object is usually a GameObject
+// with no name.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CreateEvent {
+    pub creator: Target,
+    pub object: Target,
+    pub spell: SpellParameters,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ResurrectEvent {
+    pub caster: Target,
+    pub target: Target,
+    pub spell: SpellParameters,
+}
+
+// The killing blow landed by someone in the recording player's group
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PartyKillEvent {
+    pub killer: Target,
+    pub victim: Target,
+    pub unconscious_on_death: u32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct InstakillEvent {
+    pub killer: Target,
+    pub victim: Target,
+    pub spell: SpellParameters,
+    pub unconscious_on_death: u32,
+}
+
+impl Event<'_> {
+    pub fn into_owned(self) -> Event<'static> {
+        match self {
+            Self::LogVersion(e) => Event::LogVersion(e.into_owned()),
+            Self::Combat(e) => Event::Combat(e.into_owned()),
+            Self::Stagger(e) => Event::Stagger(e),
+            Self::Combatant(e) => Event::Combatant(e),
+            Self::EncounterStart(e) => Event::EncounterStart(e.into_owned()),
+            Self::EncounterEnd(e) => Event::EncounterEnd(e.into_owned()),
+            Self::ChallengeModeStart(e) => Event::ChallengeModeStart(e.into_owned()),
+            Self::ChallengeModeEnd(e) => Event::ChallengeModeEnd(e),
+            Self::ArenaStart(e) => Event::ArenaStart(e.into_owned()),
+            Self::ArenaEnd(e) => Event::ArenaEnd(e),
+            Self::WorldMarkerPlaced(e) => Event::WorldMarkerPlaced(e),
+            Self::WorldMarkerRemoved(e) => Event::WorldMarkerRemoved(e),
+            Self::ZoneChange(e) => Event::ZoneChange(e.into_owned()),
+            Self::MapChange(e) => Event::MapChange(e.into_owned()),
+            Self::Enchant(e) => Event::Enchant(e.into_owned()),
+            Self::Emote(e) => Event::Emote(e),
+            Self::Unknown { name, raw_args } => Event::Unknown {
+                name: owned(name),
+                raw_args: owned(raw_args),
+            },
+            Self::Placeholder => Event::Placeholder,
+        }
+    }
+}
+
+impl LogVersionEvent<'_> {
+    pub fn into_owned(self) -> LogVersionEvent<'static> {
+        LogVersionEvent {
+            version: self.version,
+            advanced_log: self.advanced_log,
+            build: owned(self.build),
+        }
+    }
+}
+
+impl CombatEvent<'_> {
+    pub fn into_owned(self) -> CombatEvent<'static> {
+        CombatEvent {
+            src: self.src,
+            dst: self.dst,
+            spell: self.spell,
+            adv: self.adv,
+            environmental: self.environmental,
+            suffix: self.suffix.map(Suffix::into_owned),
+        }
+    }
+
+    pub fn summon(&self) -> Option<SummonEvent> {
+        match (&self.suffix, self.src, self.dst, self.spell) {
+            (Some(Suffix::Summon), Some(owner), Some(pet), Some(spell)) => {
+                Some(SummonEvent { owner, pet, spell })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn create(&self) -> Option<CreateEvent> {
+        match (&self.suffix, self.src, self.dst, self.spell) {
+            (Some(Suffix::Create), Some(creator), Some(object), Some(spell)) => Some(CreateEvent {
+                creator,
+                object,
+                spell,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn resurrect(&self) -> Option<ResurrectEvent> {
+        match (&self.suffix, self.src, self.dst, self.spell) {
+            (Some(Suffix::Resurrect), Some(caster), Some(target), Some(spell)) => {
+                Some(ResurrectEvent {
+                    caster,
+                    target,
+                    spell,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    // The Augmentation Evoker credited on `_SUPPORT` events
+    pub fn supporter(&self) -> Option<Guid> {
+        match &self.suffix {
+            Some(Suffix::Damage(damage)) => damage.supporter,
+            Some(Suffix::Heal(heal)) => heal.supporter,
+            Some(Suffix::Absorbed(absorb)) => absorb.supporter,
+            _ => None,
+        }
+    }
+
+    pub fn party_kill(&self) -> Option<PartyKillEvent> {
+        match (&self.suffix, self.src, self.dst) {
+            (Some(Suffix::PartyKill(unconscious)), Some(killer), Some(victim)) => {
+                Some(PartyKillEvent {
+                    killer,
+                    victim,
+                    unconscious_on_death: *unconscious,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn instakill(&self) -> Option<InstakillEvent> {
+        match (&self.suffix, self.src, self.dst, self.spell) {
+            (Some(Suffix::Instakill(unconscious)), Some(killer), Some(victim), Some(spell)) => {
+                Some(InstakillEvent {
+                    killer,
+                    victim,
+                    spell,
+                    unconscious_on_death: *unconscious,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Suffix<'_> {
+    pub fn into_owned(self) -> Suffix<'static> {
+        match self {
+            Self::Damage(e) => Suffix::Damage(e),
+            Self::Missed(e) => Suffix::Missed(e),
+            Self::Heal(e) => Suffix::Heal(e),
+            Self::HealAbsorbed(e) => Suffix::HealAbsorbed(e),
+            Self::Fail(e) => Suffix::Fail(e.into_owned()),
+            Self::Absorbed(e) => Suffix::Absorbed(e),
+            Self::Energize(e) => Suffix::Energize(e),
+            Self::Drain(e) => Suffix::Drain(e),
+            Self::Leech(e) => Suffix::Leech(e),
+            Self::Interrupt(e) => Suffix::Interrupt(e),
+            Self::Dispel(e) => Suffix::Dispel(e),
+            Self::DispelFailed(e) => Suffix::DispelFailed(e),
+            Self::Stolen(e) => Suffix::Stolen(e),
+            Self::ExtraAttacks(e) => Suffix::ExtraAttacks(e),
+            Self::Aura(e) => Suffix::Aura(e),
+            Self::AuraBroken(e) => Suffix::AuraBroken(e),
+            Self::AuraBrokenSpell(e) => Suffix::AuraBrokenSpell(e),
+            Self::Empower(e) => Suffix::Empower(e),
+            Self::Enchant(e) => Suffix::Enchant(e.into_owned()),
+            Self::UnitDied(e) => Suffix::UnitDied(e),
+            Self::UnitDestroyed(e) => Suffix::UnitDestroyed(e),
+            Self::UnitDissipates(e) => Suffix::UnitDissipates(e),
+            Self::PartyKill(e) => Suffix::PartyKill(e),
+            Self::Summon => Suffix::Summon,
+            Self::Create => Suffix::Create,
+            Self::Resurrect => Suffix::Resurrect,
+            Self::Instakill(e) => Suffix::Instakill(e),
+        }
+    }
+}
+
+impl FailEvent<'_> {
+    pub fn into_owned(self) -> FailEvent<'static> {
+        FailEvent {
+            msg: owned(self.msg),
+        }
+    }
+}
+
+impl EnchantEvent<'_> {
+    pub fn into_owned(self) -> EnchantEvent<'static> {
+        EnchantEvent {
+            name: owned(self.name),
+            item_id: self.item_id,
+            item_name: owned(self.item_name),
+        }
+    }
+}
+
+impl EncounterStartEvent<'_> {
+    pub fn into_owned(self) -> EncounterStartEvent<'static> {
+        EncounterStartEvent {
+            encounter_id: self.encounter_id,
+            encounter_name: owned(self.encounter_name),
+            difficulty: self.difficulty,
+            group_size: self.group_size,
+            instance_id: self.instance_id,
+        }
+    }
+}
+
+impl EncounterEndEvent<'_> {
+    pub fn into_owned(self) -> EncounterEndEvent<'static> {
+        EncounterEndEvent {
+            encounter_id: self.encounter_id,
+            encounter_name: owned(self.encounter_name),
+            difficulty: self.difficulty,
+            group_size: self.group_size,
+            success: self.success,
+            fight_time: self.fight_time,
+        }
+    }
+}
+
+impl ChallengeModeStartEvent<'_> {
+    pub fn into_owned(self) -> ChallengeModeStartEvent<'static> {
+        ChallengeModeStartEvent {
+            zone_name: owned(self.zone_name),
+            instance_id: self.instance_id,
+            challenge_mode_id: self.challenge_mode_id,
+            keystone_level: self.keystone_level,
+            affixes: self.affixes,
+        }
+    }
+}
+
+impl ArenaStartEvent<'_> {
+    pub fn into_owned(self) -> ArenaStartEvent<'static> {
+        ArenaStartEvent {
+            instance_id: self.instance_id,
+            unk: self.unk,
+            match_type: owned(self.match_type),
+            team_id: self.team_id,
+        }
+    }
+}
+
+impl MapChangeEvent<'_> {
+    pub fn into_owned(self) -> MapChangeEvent<'static> {
+        MapChangeEvent {
+            map_id: self.map_id,
+            map_name: owned(self.map_name),
+            x0: self.x0,
+            x1: self.x1,
+            y0: self.y0,
+            y1: self.y1,
+        }
+    }
+}
+
+impl ZoneChangeEvent<'_> {
+    pub fn into_owned(self) -> ZoneChangeEvent<'static> {
+        ZoneChangeEvent {
+            instance_id: self.instance_id,
+            zone_name: owned(self.zone_name),
+            difficulty: self.difficulty,
+        }
+    }
+}
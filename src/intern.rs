@@ -0,0 +1,179 @@
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::types::EventType;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Debug, Default)]
+struct Strings {
+    lookup: HashMap<Arc<str>, Symbol>,
+    values: Vec<Arc<str>>,
+}
+
+// Shared between every parser thread working on the same log (or set of logs)
+// so a symbol means the same string no matter which chunk produced it
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: Mutex<Strings>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&self, value: &str) -> Symbol {
+        let mut strings = self.strings.lock().expect("interner lock poisoned");
+        if let Some(symbol) = strings.lookup.get(value) {
+            return *symbol;
+        }
+
+        let symbol = Symbol(
+            u32::try_from(strings.values.len()).expect("fewer than u32::MAX unique strings"),
+        );
+        let value: Arc<str> = Arc::from(value);
+        strings.values.push(value.clone());
+        strings.lookup.insert(value, symbol);
+
+        symbol
+    }
+
+    pub fn get(&self, value: &str) -> Option<Symbol> {
+        let strings = self.strings.lock().expect("interner lock poisoned");
+        strings.lookup.get(value).copied()
+    }
+
+    pub fn table(&self) -> SymbolTable {
+        let strings = self.strings.lock().expect("interner lock poisoned");
+        SymbolTable {
+            values: strings.values.clone(),
+        }
+    }
+}
+
+// Per-thread front for the shared interner. A log only holds a few hundred
+// distinct names so almost every lookup is answered here without touching
+// the lock.
+#[derive(Debug)]
+pub struct SymbolCache {
+    interner: Arc<Interner>,
+    local: RefCell<HashMap<Box<str>, Symbol>>,
+}
+
+impl SymbolCache {
+    pub fn new(interner: Arc<Interner>) -> Self {
+        Self {
+            interner,
+            local: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn intern(&self, value: &str) -> Symbol {
+        if let Some(symbol) = self.local.borrow().get(value) {
+            return *symbol;
+        }
+
+        let symbol = self.interner.intern(value);
+        self.local.borrow_mut().insert(Box::from(value), symbol);
+        symbol
+    }
+
+    pub fn interner(&self) -> &Arc<Interner> {
+        &self.interner
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    values: Vec<Arc<str>>,
+}
+
+impl SymbolTable {
+    pub fn resolve(&self, symbol: impl Into<Symbol>) -> &str {
+        let symbol = symbol.into();
+        self.get(symbol)
+            .unwrap_or_else(|| panic!("symbol {} is not from this table", symbol.0))
+    }
+
+    pub fn get(&self, symbol: impl Into<Symbol>) -> Option<&str> {
+        self.values.get(symbol.into().index()).map(|v| v.as_ref())
+    }
+
+    // Event types the parser didn't recognise only carry the symbol of their
+    // name
+    pub fn event_name(&self, event_type: EventType) -> Cow<'_, str> {
+        match event_type {
+            EventType::Unknown(name) => Cow::Borrowed(self.resolve(name)),
+            other => Cow::Owned(other.to_string()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod intern_tests {
+    use super::*;
+
+    #[test]
+    fn symbols_are_shared_across_caches() {
+        let interner = Arc::new(Interner::new());
+        let first = SymbolCache::new(interner.clone());
+        let second = SymbolCache::new(interner.clone());
+
+        let a = first.intern("Player-1-0001");
+        let b = second.intern("Creature-0-1-2-3-4-5");
+        assert_eq!(second.intern("Player-1-0001"), a);
+        assert_eq!(first.intern("Creature-0-1-2-3-4-5"), b);
+        assert_ne!(a, b);
+
+        let table = interner.table();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.resolve(a), "Player-1-0001");
+        assert_eq!(table.resolve(b), "Creature-0-1-2-3-4-5");
+    }
+
+    #[test]
+    fn interning_from_many_threads_is_consistent() {
+        let interner = Arc::new(Interner::new());
+        let names = (0..64).map(|i| format!("name-{i}")).collect::<Vec<_>>();
+
+        let results = std::thread::scope(|scope| {
+            (0..8)
+                .map(|_| {
+                    let cache = SymbolCache::new(interner.clone());
+                    let names = &names;
+                    scope.spawn(move || names.iter().map(|n| cache.intern(n)).collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|h| h.join().expect("interning thread panicked"))
+                .collect::<Vec<_>>()
+        });
+
+        assert!(results.windows(2).all(|pair| pair[0] == pair[1]));
+        let table = interner.table();
+        assert_eq!(table.len(), names.len());
+        for (symbol, name) in results[0].iter().zip(&names) {
+            assert_eq!(table.resolve(*symbol), name);
+        }
+    }
+}
@@ -1,58 +1,197 @@
+pub mod analysis;
+pub mod arena;
 pub mod event;
+pub mod intern;
+pub mod merge;
 pub mod parser;
 pub mod player;
+pub mod roster;
+pub mod segment;
+pub mod talents;
 pub mod types;
 
-use memmap::MmapOptions;
-use parser::{EventLogParser, ParsedEvent};
-use std::{io::BufReader, path::Path};
+#[cfg(test)]
+mod testing;
+
+use event::Event;
+use intern::{Interner, Symbol, SymbolTable};
+use memmap::{Mmap, MmapOptions};
+use parser::{BorrowedEventParser, LineParser, ParsedEvent};
+use std::{ops::Range, path::Path, sync::Arc};
+use types::{Dialect, EventType};
+
+#[derive(Debug, Clone)]
+pub struct ParsedLog<'a> {
+    pub events: Vec<ParsedEvent<'a>>,
+    pub symbols: SymbolTable,
+}
+
+impl ParsedLog<'_> {
+    pub fn into_owned(self) -> ParsedLog<'static> {
+        ParsedLog {
+            events: self
+                .events
+                .into_iter()
+                .map(ParsedEvent::into_owned)
+                .collect(),
+            symbols: self.symbols,
+        }
+    }
+
+    // Event types the parser didn't recognise and how often each was seen, in
+    // the order they first appear
+    pub fn unrecognised(&self) -> Vec<(&str, usize)> {
+        let mut counts: Vec<(Symbol, usize)> = Vec::new();
+        for event in &self.events {
+            let EventType::Unknown(name) = event.event_type else {
+                continue;
+            };
+
+            match counts.iter_mut().find(|(seen, _)| *seen == name) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((name, 1)),
+            }
+        }
+
+        counts
+            .into_iter()
+            .map(|(name, count)| (self.symbols.resolve(name), count))
+            .collect()
+    }
+}
 
-pub struct LogFile;
+pub struct LogFile {
+    map: Mmap,
+}
 
 impl LogFile {
-    pub fn parse(path: impl AsRef<Path>) -> eyre::Result<Vec<ParsedEvent>> {
+    pub fn open(path: impl AsRef<Path>) -> eyre::Result<Self> {
         let file = std::fs::File::open(path)?;
 
         // Safety: We can guarantee that the file is not modified underneath
         // as these are static logs that don't change
         let map = unsafe { MmapOptions::new().map(&file)? };
+        Ok(Self { map })
+    }
+
+    // Events borrow their strings from the mapped file so nothing is copied
+    // per line. Use `ParsedLog::into_owned` on anything that has to outlive
+    // the `LogFile`.
+    pub fn events(&self) -> eyre::Result<ParsedLog<'_>> {
+        let interner = Arc::new(Interner::new());
+        let events = self.events_with(&interner)?;
+
+        Ok(ParsedLog {
+            events,
+            symbols: interner.table(),
+        })
+    }
+
+    // Parses against an existing interner so symbols can be compared between
+    // several logs of the same night
+    pub fn events_with(&self, interner: &Arc<Interner>) -> eyre::Result<Vec<ParsedEvent<'_>>> {
+        let n = std::thread::available_parallelism().expect("a valid value for parallelism");
+        Self::parse_bytes(&self.map, n.get(), interner)
+    }
+
+    pub fn parse(path: impl AsRef<Path>) -> eyre::Result<ParsedLog<'static>> {
+        let log = Self::open(path)?;
+        Ok(log.events()?.into_owned())
+    }
+
+    // Timestamps are local wall-clock time so they repeat and go backwards
+    // across midnight and DST changes. Chunks are joined in the order they
+    // were cut from the file rather than sorted so the original line order
+    // is kept exactly.
+    //
+    // Every chunk starts out assuming the dialect from the top of the file.
+    // A client reloading with advanced logging toggled writes a new header
+    // part way through, so any chunk after it that guessed wrong, or failed
+    // outright, is parsed again with the dialect the previous chunk ended on.
+    fn parse_bytes<'a>(
+        buf: &'a [u8],
+        threads: usize,
+        interner: &Arc<Interner>,
+    ) -> eyre::Result<Vec<ParsedEvent<'a>>> {
+        let initial = Self::dialect(buf, interner).unwrap_or_default();
+        let chunks = chunk_boundaries(buf, threads);
         std::thread::scope(|scope| {
-            let n = std::thread::available_parallelism().expect("a valid value for parallelism");
-            let chunk = map.len() / n.get();
-            let mut at = 0;
-            let (tx, rx) = std::sync::mpsc::sync_channel::<Vec<ParsedEvent>>(n.get());
-
-            for _ in 0..n.get() {
-                let start = at;
-                let end = (at + chunk).min(map.len());
-                let end = if end == map.len() {
-                    map.len()
-                } else {
-                    let buf = &map[end..];
-                    let newline = buf.iter().position(|b| *b == b'\n').unwrap_or(buf.len());
-                    end + newline + 1
+            let handles = chunks
+                .iter()
+                .map(|range| {
+                    let chunk = &buf[range.clone()];
+                    let interner = interner.clone();
+                    scope.spawn(move || Self::parse_chunk(chunk, interner, initial))
+                })
+                .collect::<Vec<_>>();
+
+            let mut events = Vec::new();
+            let mut dialect = initial;
+            for (range, handle) in chunks.iter().zip(handles) {
+                let (chunk_events, end) = match handle.join() {
+                    Ok(Ok(parsed)) if dialect == initial => parsed,
+                    _ => Self::parse_chunk(&buf[range.clone()], interner.clone(), dialect)?,
                 };
-                let reader = BufReader::new(&map[start..end]);
-                let parser = EventLogParser::new(reader);
-                at = end;
-
-                let tx = tx.clone();
-                scope.spawn(move || {
-                    tx.send(
-                        parser
-                            .into_iter()
-                            .map(|event| event.expect("a valid event"))
-                            .collect::<Vec<ParsedEvent>>(),
-                    )
-                });
+
+                events.extend(chunk_events);
+                dialect = end;
             }
 
-            drop(tx);
-            let mut events = rx.into_iter().collect::<Vec<Vec<ParsedEvent>>>();
-            events.sort_by(|a, b| a[0].timestamp.cmp(&b[0].timestamp));
-            Ok(events.into_iter().flatten().collect::<Vec<ParsedEvent>>())
+            Ok(events)
         })
     }
+
+    fn parse_chunk(
+        chunk: &[u8],
+        interner: Arc<Interner>,
+        dialect: Dialect,
+    ) -> eyre::Result<(Vec<ParsedEvent<'_>>, Dialect)> {
+        let mut parser = BorrowedEventParser::with_interner(std::str::from_utf8(chunk)?, interner)
+            .with_dialect(dialect);
+        let events = parser
+            .by_ref()
+            .collect::<eyre::Result<Vec<ParsedEvent>>>()?;
+
+        Ok((events, parser.dialect()))
+    }
+
+    // The COMBAT_LOG_VERSION line is the first line of every log. Anything
+    // else (or a header that fails to parse) is left to the chunk parsers to
+    // report and the default dialect is used.
+    fn dialect(buf: &[u8], interner: &Arc<Interner>) -> Option<Dialect> {
+        let end = buf.iter().position(|b| *b == b'\n').unwrap_or(buf.len());
+        let line = std::str::from_utf8(&buf[..end]).ok()?.trim();
+        if line.is_empty() {
+            return None;
+        }
+
+        let parser = LineParser::new(interner.clone());
+        match parser.parse_event(line).ok()?.event {
+            Event::LogVersion(_) => Some(parser.dialect()),
+            _ => None,
+        }
+    }
+}
+
+// Splits the buffer into at most `n` ranges that each end on a line boundary.
+// Small files can have fewer lines than threads so empty ranges are dropped.
+fn chunk_boundaries(buf: &[u8], n: usize) -> Vec<Range<usize>> {
+    let chunk = buf.len().div_ceil(n.max(1)).max(1);
+    let mut ranges = Vec::with_capacity(n);
+    let mut start = 0;
+
+    while start < buf.len() {
+        let end = (start + chunk).min(buf.len());
+        let end = match buf[end - 1..].iter().position(|b| *b == b'\n') {
+            Some(newline) => end + newline,
+            None => buf.len(),
+        };
+
+        ranges.push(start..end);
+        start = end;
+    }
+
+    ranges
 }
 
 #[cfg(test)]
@@ -71,4 +210,215 @@ mod jastor_tests {
 
         Ok(())
     }
+
+    fn parse(buf: &[u8], threads: usize) -> eyre::Result<Vec<ParsedEvent<'_>>> {
+        LogFile::parse_bytes(buf, threads, &Arc::new(Interner::new()))
+    }
+
+    const HEADER: &str = "4/19/2026 23:59:58.000  COMBAT_LOG_VERSION,22,ADVANCED_LOG_ENABLED,1,BUILD_VERSION,11.1.5,PROJECT_ID,1\n";
+
+    fn zone_change(ts: &str, instance_id: u32) -> String {
+        format!("{ts}  ZONE_CHANGE,{instance_id},\"Zone\",0\n")
+    }
+
+    fn instance_ids(events: &[ParsedEvent]) -> Vec<u32> {
+        events
+            .iter()
+            .filter_map(|event| match &event.event {
+                event::Event::ZoneChange(zone) => Some(zone.instance_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chunks_cover_the_buffer_on_line_boundaries() {
+        let buf = b"a\nbb\nccc\ndddd";
+        for n in 1..=16 {
+            let ranges = chunk_boundaries(buf, n);
+            assert!(ranges.len() <= n);
+            assert_eq!(ranges.first().map(|r| r.start), Some(0));
+            assert_eq!(ranges.last().map(|r| r.end), Some(buf.len()));
+            for pair in ranges.windows(2) {
+                assert_eq!(pair[0].end, pair[1].start);
+                assert_eq!(buf[pair[0].end - 1], b'\n');
+            }
+            assert!(ranges.iter().all(|r| !r.is_empty()));
+        }
+
+        assert!(chunk_boundaries(b"", 4).is_empty());
+    }
+
+    #[test]
+    fn tiny_files_parse_with_more_threads_than_lines() -> eyre::Result<()> {
+        let log = format!("{HEADER}{}", zone_change("4/19/2026 23:59:59.000", 1));
+        for threads in 1..=32 {
+            let events = parse(log.as_bytes(), threads)?;
+            assert_eq!(events.len(), 2);
+        }
+
+        assert!(parse(b"", 8)?.is_empty());
+        assert!(parse(b"\n\n", 8)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn file_order_is_kept_across_midnight_and_dst() -> eyre::Result<()> {
+        let log = [
+            HEADER.to_string(),
+            zone_change("4/19/2026 23:59:59.000", 1),
+            zone_change("4/19/2026 23:59:59.000", 2),
+            zone_change("4/20/2026 00:00:00.000", 3),
+            // Clocks going back an hour at the end of summer time
+            zone_change("10/25/2026 01:59:59.000", 4),
+            zone_change("10/25/2026 01:00:00.000", 5),
+            zone_change("10/25/2026 01:00:00.000", 6),
+            zone_change("10/25/2026 01:30:00.000", 7),
+        ]
+        .concat();
+
+        for threads in 1..=12 {
+            let events = parse(log.as_bytes(), threads)?;
+            assert_eq!(instance_ids(&events), vec![1, 2, 3, 4, 5, 6, 7]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn final_line_without_newline_is_parsed() -> eyre::Result<()> {
+        let log = format!("{HEADER}{}", zone_change("4/19/2026 23:59:59.000", 1));
+        let log = log.trim_end();
+        for threads in 1..=8 {
+            let events = parse(log.as_bytes(), threads)?;
+            assert_eq!(instance_ids(&events), vec![1]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn every_chunk_uses_the_layout_from_the_header() -> eyre::Result<()> {
+        let header = HEADER.replace("COMBAT_LOG_VERSION,22", "COMBAT_LOG_VERSION,20");
+        let cast = "4/19/2026 23:59:59.000  SPELL_CAST_SUCCESS,Player-1-0001,\"Tank-Realm\",0x511,0x0,0000000000000000,nil,0x80000000,0x80000000,6673,\"Battle Shout\",0x1,Player-1-0001,0000000000000000,100,200,300,400,500,50,0,100,100,0,-10.5,20.25,2769,1.5,639\n";
+        let log = format!("{header}{}", cast.repeat(16));
+
+        for threads in 1..=8 {
+            let events = parse(log.as_bytes(), threads)?;
+            assert_eq!(events.len(), 17);
+            assert!(events[1..].iter().all(|event| {
+                match &event.event {
+                    event::Event::Combat(combat) => combat
+                        .adv
+                        .as_ref()
+                        .is_some_and(|adv| adv.absorb == 50 && adv.item_level == Some(639)),
+                    _ => false,
+                }
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn advanced_logging_can_be_toggled_mid_file() -> eyre::Result<()> {
+        let cast = |ts: &str, adv: &str| {
+            format!(
+                "4/19/2026 {ts}  SPELL_CAST_SUCCESS,Player-1-0001,\"Tank-Realm\",0x511,0x0,0000000000000000,nil,0x80000000,0x80000000,6673,\"Battle Shout\",0x1{adv}\n"
+            )
+        };
+        let adv = ",Player-1-0001,0000000000000000,100,200,300,400,500,7,8,50,0,100,100,0,-10.5,20.25,2769,1.5,639";
+        let reload = HEADER.replace("ADVANCED_LOG_ENABLED,1", "ADVANCED_LOG_ENABLED,0");
+
+        let log = [
+            HEADER.to_string(),
+            cast("23:59:59.000", adv).repeat(8),
+            reload.clone(),
+            cast("23:59:59.500", "").repeat(8),
+            HEADER.to_string(),
+            cast("23:59:59.900", adv).repeat(8),
+        ]
+        .concat();
+
+        for threads in 1..=16 {
+            let events = parse(log.as_bytes(), threads)?;
+            let advanced = events
+                .iter()
+                .filter_map(|event| match &event.event {
+                    event::Event::Combat(combat) => Some(combat.adv.is_some()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(events.len(), 27);
+            assert_eq!(advanced[..8], [true; 8]);
+            assert_eq!(advanced[8..16], [false; 8]);
+            assert_eq!(advanced[16..], [true; 8]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn chunks_in_the_other_dialect_are_parsed_again() -> eyre::Result<()> {
+        let swing = |ts: &str, adv: &str| {
+            format!(
+                "4/19/2026 {ts}  SWING_DAMAGE,Player-1-0001,\"Tank-Realm\",0x511,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0{adv},1200,1000,-1,1,0,0,0,1,nil,nil,nil\n"
+            )
+        };
+        let adv = ",Player-1-0001,0000000000000000,100,200,300,400,500,50,0,100,100,0,-10.5,20.25,2769,1.5,639";
+        let header = HEADER.replace("COMBAT_LOG_VERSION,22", "COMBAT_LOG_VERSION,20");
+        let reload = header.replace("ADVANCED_LOG_ENABLED,1", "ADVANCED_LOG_ENABLED,0");
+
+        let log = [
+            header,
+            swing("23:59:59.000", adv).repeat(4),
+            reload,
+            swing("23:59:59.500", "").repeat(32),
+        ]
+        .concat();
+
+        for threads in 1..=16 {
+            let events = parse(log.as_bytes(), threads)?;
+            assert_eq!(events.len(), 38);
+            assert!(events[6..].iter().all(|event| match &event.event {
+                event::Event::Combat(combat) => combat.adv.is_none(),
+                _ => false,
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn unrecognised_events_are_counted() -> eyre::Result<()> {
+        let log = [
+            HEADER,
+            "4/19/2026 23:59:59.000  NEW_EVENT,1,2\n",
+            &zone_change("4/19/2026 23:59:59.500", 1),
+            "4/19/2026 23:59:59.600  OTHER_EVENT,nil\n",
+            "4/19/2026 23:59:59.700  NEW_EVENT,3\n",
+        ]
+        .concat();
+
+        let interner = Arc::new(Interner::new());
+        let log = ParsedLog {
+            events: LogFile::parse_bytes(log.as_bytes(), 2, &interner)?,
+            symbols: interner.table(),
+        };
+        assert_eq!(
+            log.unrecognised(),
+            vec![("NEW_EVENT", 2), ("OTHER_EVENT", 1)]
+        );
+        assert_eq!(
+            log.symbols.event_name(log.events[1].event_type),
+            "NEW_EVENT"
+        );
+        assert_eq!(
+            log.symbols.event_name(log.events[2].event_type),
+            "ZONE_CHANGE"
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,474 @@
+use std::{collections::VecDeque, path::Path, sync::Arc};
+
+use eyre::Result;
+use jiff::{SignedDuration, civil::DateTime};
+
+use crate::{
+    LogFile,
+    event::{Difficulty, Event, Target},
+    intern::{Interner, SymbolTable},
+    parser::ParsedEvent,
+};
+
+// Clients in the same group log the same fight a few milliseconds apart
+const DEFAULT_TOLERANCE: SignedDuration = SignedDuration::from_millis(250);
+
+// Encounter starts seen by two clients agree to within this window once the
+// clock offset between them has been removed
+const ALIGNMENT_WINDOW: SignedDuration = SignedDuration::from_secs(1);
+
+// A source that writes nothing for this long is taken to have stopped
+// logging, e.g. after a disconnect
+const COVERAGE_GAP: SignedDuration = SignedDuration::from_secs(120);
+
+#[derive(Debug, Clone)]
+pub struct MergedLog<'a> {
+    pub events: Vec<ParsedEvent<'a>>,
+    pub symbols: SymbolTable,
+    pub report: MergeReport,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MergeReport {
+    pub sources: Vec<SourceSummary>,
+    pub duplicates: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceSummary {
+    pub name: String,
+    pub clock_offset: SignedDuration,
+    pub events: usize,
+    pub contributed: usize,
+    // When the source was logging, on the merged clock
+    pub coverage: Vec<Coverage>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coverage {
+    pub start: DateTime,
+    pub end: DateTime,
+    pub events: usize,
+    // Events from this stretch that were kept rather than dropped as
+    // duplicates of another source
+    pub contributed: usize,
+}
+
+struct MergeSource<'a> {
+    name: String,
+    events: Vec<ParsedEvent<'a>>,
+}
+
+struct Kept {
+    index: usize,
+    source: usize,
+    seen_by: Vec<usize>,
+}
+
+pub struct LogMerger<'a> {
+    sources: Vec<MergeSource<'a>>,
+    interner: Arc<Interner>,
+    tolerance: SignedDuration,
+}
+
+impl Default for LogMerger<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> LogMerger<'a> {
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+            interner: Arc::new(Interner::new()),
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    // Events handed to `add_events` have to be parsed against this interner
+    // so that the same unit or spell gets the same symbol in every source
+    pub fn interner(&self) -> &Arc<Interner> {
+        &self.interner
+    }
+
+    pub fn with_tolerance(mut self, tolerance: SignedDuration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let events = LogFile::open(path)?
+            .events_with(&self.interner)?
+            .into_iter()
+            .map(ParsedEvent::into_owned)
+            .collect::<Vec<_>>();
+        self.add_events(path.display().to_string(), events);
+
+        Ok(())
+    }
+
+    pub fn add_events(&mut self, name: impl Into<String>, events: Vec<ParsedEvent<'a>>) {
+        self.sources.push(MergeSource {
+            name: name.into(),
+            events,
+        });
+    }
+
+    pub fn merge(self) -> MergedLog<'a> {
+        let offsets = self.clock_offsets();
+        let mut report = MergeReport {
+            sources: self
+                .sources
+                .iter()
+                .zip(&offsets)
+                .map(|(source, offset)| SourceSummary {
+                    name: source.name.clone(),
+                    clock_offset: *offset,
+                    events: source.events.len(),
+                    contributed: 0,
+                    coverage: coverage(&source.events, *offset),
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        let mut streams = self
+            .sources
+            .into_iter()
+            .zip(&offsets)
+            .map(|(source, offset)| {
+                source
+                    .events
+                    .into_iter()
+                    .map(|mut event| {
+                        event.timestamp = event.timestamp.saturating_add(*offset);
+                        event
+                    })
+                    .peekable()
+            })
+            .collect::<Vec<_>>();
+
+        let mut events: Vec<ParsedEvent<'a>> = Vec::new();
+        let mut window: VecDeque<Kept> = VecDeque::new();
+
+        // K-way merge over the sources so each file keeps its own line order,
+        // ties going to the earlier source
+        while let Some(source) = (0..streams.len())
+            .filter_map(|i| streams[i].peek().map(|e| (i, e.timestamp)))
+            .min_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)))
+            .map(|(i, _)| i)
+        {
+            let event = streams[source].next().expect("peeked above");
+            let horizon = event.timestamp.saturating_sub(self.tolerance);
+            while window
+                .front()
+                .is_some_and(|kept| events[kept.index].timestamp < horizon)
+            {
+                window.pop_front();
+            }
+
+            let twin = window.iter_mut().find(|kept| {
+                kept.source != source
+                    && !kept.seen_by.contains(&source)
+                    && same_event(&events[kept.index], &event)
+            });
+
+            if let Some(twin) = twin {
+                twin.seen_by.push(source);
+                report.duplicates += 1;
+                continue;
+            }
+
+            window.push_back(Kept {
+                index: events.len(),
+                source,
+                seen_by: Vec::new(),
+            });
+            let summary = &mut report.sources[source];
+            summary.contributed += 1;
+            let range = summary
+                .coverage
+                .iter()
+                .rposition(|range| range.start <= event.timestamp)
+                .unwrap_or_default();
+            summary.coverage[range].contributed += 1;
+            events.push(event);
+        }
+
+        MergedLog {
+            events,
+            symbols: self.interner.table(),
+            report,
+        }
+    }
+
+    // Aligns every source onto the clock of the first one using the encounter
+    // starts they share. Sources can be aligned through any earlier source so
+    // a chain of overlapping logs still ends up on a single clock.
+    fn clock_offsets(&self) -> Vec<SignedDuration> {
+        let mut offsets: Vec<Option<SignedDuration>> = vec![None; self.sources.len()];
+        if let Some(first) = offsets.first_mut() {
+            *first = Some(SignedDuration::ZERO);
+        }
+
+        let starts = self
+            .sources
+            .iter()
+            .map(|source| encounter_starts(&source.events))
+            .collect::<Vec<_>>();
+
+        let mut progress = true;
+        while progress {
+            progress = false;
+            for current in 0..self.sources.len() {
+                if offsets[current].is_some() {
+                    continue;
+                }
+
+                let candidates = (0..self.sources.len())
+                    .filter_map(|aligned| offsets[aligned].map(|offset| (aligned, offset)))
+                    .flat_map(|(aligned, offset)| {
+                        let theirs = &starts[aligned];
+                        starts[current].iter().flat_map(move |(key, ts)| {
+                            theirs
+                                .iter()
+                                .filter(move |(other, _)| other == key)
+                                .map(move |(_, other_ts)| other_ts.duration_since(*ts) + offset)
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                // The true offset is shared by every genuine pair of pulls while
+                // mismatched pulls of the same boss scatter, so take the mode
+                let best = candidates.iter().max_by_key(|candidate| {
+                    candidates
+                        .iter()
+                        .filter(|other| (**other - **candidate).abs() <= ALIGNMENT_WINDOW)
+                        .count()
+                });
+
+                if let Some(best) = best {
+                    offsets[current] = Some(*best);
+                    progress = true;
+                }
+            }
+        }
+
+        offsets
+            .into_iter()
+            .map(|offset| offset.unwrap_or(SignedDuration::ZERO))
+            .collect()
+    }
+}
+
+fn encounter_starts(events: &[ParsedEvent<'_>]) -> Vec<((u32, Difficulty), DateTime)> {
+    events
+        .iter()
+        .filter_map(|event| match &event.event {
+            Event::EncounterStart(start) => {
+                Some(((start.encounter_id, start.difficulty), event.timestamp))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// Stretches of the source's own events with no gap longer than
+// `COVERAGE_GAP`, so interleaving with other clients doesn't split them
+fn coverage(events: &[ParsedEvent<'_>], offset: SignedDuration) -> Vec<Coverage> {
+    let mut ranges: Vec<Coverage> = Vec::new();
+
+    for event in events {
+        let timestamp = event.timestamp.saturating_add(offset);
+        match ranges.last_mut() {
+            Some(range) if timestamp.duration_since(range.end) <= COVERAGE_GAP => {
+                range.end = range.end.max(timestamp);
+                range.events += 1;
+            }
+            _ => ranges.push(Coverage {
+                start: timestamp,
+                end: timestamp,
+                events: 1,
+                contributed: 0,
+            }),
+        }
+    }
+
+    ranges
+}
+
+// Advanced parameters are a snapshot of the unit as each client saw it at the
+// time so positions and resources drift between logs of the same event. Unit
+// flags are relative to whoever recorded the log so they differ as well.
+fn same_event(a: &ParsedEvent<'_>, b: &ParsedEvent<'_>) -> bool {
+    if a.event_type != b.event_type {
+        return false;
+    }
+
+    let unit = |target: &Option<Target>| target.map(|t| (t.guid, t.name));
+    match (&a.event, &b.event) {
+        (Event::Combat(a), Event::Combat(b)) => {
+            unit(&a.src) == unit(&b.src)
+                && unit(&a.dst) == unit(&b.dst)
+                && a.spell == b.spell
+                && a.environmental == b.environmental
+                && a.suffix == b.suffix
+        }
+        (a, b) => a == b,
+    }
+}
+
+#[cfg(test)]
+mod merge_tests {
+    use super::*;
+    use crate::parser::BorrowedEventParser;
+
+    fn parse<'a>(log: &'a str, merger: &LogMerger) -> Vec<ParsedEvent<'a>> {
+        BorrowedEventParser::with_interner(log, merger.interner().clone())
+            .collect::<Result<Vec<ParsedEvent>>>()
+            .expect("valid test log")
+    }
+
+    fn at(time: &str) -> DateTime {
+        format!("2026-04-19T{time}")
+            .parse()
+            .expect("valid test time")
+    }
+
+    fn ranges(coverage: &[Coverage]) -> Vec<(DateTime, DateTime, usize, usize)> {
+        coverage
+            .iter()
+            .map(|range| (range.start, range.end, range.events, range.contributed))
+            .collect()
+    }
+
+    fn aura(ts: &str, flags: &str, spell_id: u32) -> String {
+        format!(
+            "{ts}  SPELL_AURA_APPLIED,Player-1-0001,\"Tank-Realm\",{flags},0x0,Player-1-0001,\"Tank-Realm\",{flags},0x0,{spell_id},\"Buff\",0x1,BUFF\n"
+        )
+    }
+
+    #[test]
+    fn it_aligns_and_deduplicates_overlapping_logs() {
+        let first = [
+            "4/19/2026 20:00:00.000  ENCOUNTER_START,3009,\"Vexie\",16,20,2769\n".to_string(),
+            aura("4/19/2026 20:00:01.000", "0x511", 1),
+            aura("4/19/2026 20:00:02.000", "0x511", 2),
+            aura("4/19/2026 20:00:03.000", "0x511", 3),
+        ]
+        .concat();
+
+        // Recorded an hour ahead by a raider in another time zone
+        let second = [
+            "4/19/2026 21:00:00.010  ENCOUNTER_START,3009,\"Vexie\",16,20,2769\n".to_string(),
+            aura("4/19/2026 21:00:02.020", "0x512", 2),
+            aura("4/19/2026 21:00:03.015", "0x512", 3),
+            aura("4/19/2026 21:00:04.000", "0x512", 4),
+        ]
+        .concat();
+
+        let mut merger = LogMerger::new();
+        merger.add_events("first", parse(&first, &merger));
+        merger.add_events("second", parse(&second, &merger));
+        let merged = merger.merge();
+
+        assert_eq!(merged.events.len(), 5);
+        assert_eq!(merged.report.duplicates, 3);
+        assert_eq!(
+            merged.report.sources[1].clock_offset,
+            SignedDuration::from_millis(-3_600_010)
+        );
+
+        let spells = merged
+            .events
+            .iter()
+            .filter_map(|event| match &event.event {
+                Event::Combat(combat) => combat.spell.as_ref().map(|s| s.spell_id),
+                _ => None,
+            })
+            .collect::<Vec<u32>>();
+        assert_eq!(spells, vec![1, 2, 3, 4]);
+
+        let coverage = merged
+            .report
+            .sources
+            .iter()
+            .map(|source| ranges(&source.coverage))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            coverage,
+            vec![
+                vec![(at("20:00:00"), at("20:00:03"), 4, 4)],
+                vec![(at("20:00:00"), at("20:00:03.99"), 4, 1)]
+            ]
+        );
+    }
+
+    #[test]
+    fn it_keeps_repeated_events_from_each_client() {
+        let first = [
+            aura("4/19/2026 20:00:01.000", "0x511", 1),
+            aura("4/19/2026 20:00:01.000", "0x511", 1),
+        ]
+        .concat();
+        let second = [
+            aura("4/19/2026 20:00:01.000", "0x512", 1),
+            aura("4/19/2026 20:00:01.000", "0x512", 1),
+            aura("4/19/2026 20:00:01.000", "0x512", 1),
+        ]
+        .concat();
+
+        let mut merger = LogMerger::new();
+        merger.add_events("first", parse(&first, &merger));
+        merger.add_events("second", parse(&second, &merger));
+        let merged = merger.merge();
+
+        assert_eq!(merged.events.len(), 3);
+        assert_eq!(merged.report.duplicates, 2);
+        assert_eq!(merged.report.sources[1].contributed, 1);
+    }
+
+    #[test]
+    fn coverage_is_reported_per_source() {
+        // Two clients taking turns to see events the other missed, then each
+        // going quiet for a few minutes
+        let first = [
+            aura("4/19/2026 20:00:01.000", "0x511", 1),
+            aura("4/19/2026 20:00:03.000", "0x511", 3),
+            aura("4/19/2026 20:00:05.000", "0x511", 5),
+            aura("4/19/2026 20:10:00.000", "0x511", 7),
+        ]
+        .concat();
+        let second = [
+            aura("4/19/2026 20:00:02.000", "0x512", 2),
+            aura("4/19/2026 20:00:04.000", "0x512", 4),
+            aura("4/19/2026 20:00:05.000", "0x512", 5),
+            aura("4/19/2026 20:05:00.000", "0x512", 6),
+        ]
+        .concat();
+
+        let mut merger = LogMerger::new();
+        merger.add_events("first", parse(&first, &merger));
+        merger.add_events("second", parse(&second, &merger));
+        let merged = merger.merge();
+        assert_eq!(merged.events.len(), 7);
+
+        let sources = &merged.report.sources;
+        assert_eq!(
+            ranges(&sources[0].coverage),
+            vec![
+                (at("20:00:01"), at("20:00:05"), 3, 3),
+                (at("20:10:00"), at("20:10:00"), 1, 1)
+            ]
+        );
+        assert_eq!(
+            ranges(&sources[1].coverage),
+            vec![
+                (at("20:00:02"), at("20:00:05"), 3, 2),
+                (at("20:05:00"), at("20:05:00"), 1, 1)
+            ]
+        );
+    }
+}
@@ -1,15 +1,17 @@
-use std::{io::BufRead, str::FromStr};
+use std::{borrow::Cow, cell::Cell, io::BufRead, str::FromStr, sync::Arc};
 
 use crate::{
     event::{
-        AbsorbEvent, AdvancedParameters, ArenaEndEvent, ArenaStartEvent, AuraEvent, AuraType,
-        AuraWithSpellEvent, CombatEvent, Combatant, DamageEvent, Difficulty, DrainEvent,
-        EmoteEvent, EnchantEvent, EncounterEndEvent, EncounterStartEvent, EnergizeEvent,
-        EnvironmentalType, Event, EventType, FailEvent, Guid, HealAbsorbEvent, HealEvent,
-        LogVersionEvent, MapChangeEvent, MissEvent, MissType, MultiValue, PowerType, RaidFlag,
-        SpellParameters, SpellSchool, StaggerEvent, StealEvent, StealWithAuraEvent, Suffix, Target,
-        UnitFlags, WorldMarkerPlacedEvent, ZoneChangeEvent,
+        AbsorbEvent, AdvancedLayout, AdvancedParameters, ArenaEndEvent, ArenaStartEvent, AuraEvent,
+        AuraType, AuraWithSpellEvent, ChallengeModeEndEvent, ChallengeModeStartEvent, CombatEvent,
+        Combatant, DamageEvent, Dialect, Difficulty, DrainEvent, EmoteEvent, EnchantEvent,
+        EncounterEndEvent, EncounterStartEvent, EnergizeEvent, EnvironmentalType, Event, EventType,
+        FailEvent, Guid, HealAbsorbEvent, HealEvent, LogVersionEvent, MapChangeEvent, MissEvent,
+        MissType, MultiValue, PowerType, RaidFlag, SpellParameters, SpellSchool, StaggerEvent,
+        StealEvent, StealWithAuraEvent, Suffix, Target, UnitFlags, WorldMarkerPlacedEvent,
+        ZoneChangeEvent,
     },
+    intern::{Interner, SymbolCache, SymbolTable},
     types::CastType,
 };
 
@@ -17,24 +19,172 @@ use eyre::{Context, Result, eyre};
 use jiff::{civil::DateTime, fmt::strtime};
 use num::Num;
 
-#[derive(Debug, Clone)]
-pub struct ParsedEvent {
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedEvent<'a> {
     pub timestamp: DateTime,
     pub event_type: EventType,
-    pub event: Event,
+    pub event: Event<'a>,
+}
+
+impl ParsedEvent<'_> {
+    pub fn into_owned(self) -> ParsedEvent<'static> {
+        ParsedEvent {
+            timestamp: self.timestamp,
+            event_type: self.event_type,
+            event: self.event.into_owned(),
+        }
+    }
 }
 
 pub struct EventLogParser<R: BufRead> {
     reader: R,
+    parser: LineParser,
 }
 
 impl<R: BufRead> EventLogParser<R> {
     pub fn new(reader: R) -> Self {
-        Self { reader }
+        Self::with_interner(reader, Arc::new(Interner::new()))
+    }
+
+    pub fn with_interner(reader: R, interner: Arc<Interner>) -> Self {
+        Self {
+            reader,
+            parser: LineParser::new(interner),
+        }
+    }
+
+    pub fn symbols(&self) -> SymbolTable {
+        self.parser.symbols()
+    }
+}
+
+impl<R: BufRead> Iterator for EventLogParser<R> {
+    type Item = Result<ParsedEvent<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = String::new();
+        loop {
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) if line.trim().is_empty() => line.clear(),
+                Ok(_) => break,
+                Err(e) => {
+                    eprintln!("error occurred parsing line - {e}");
+                    return None;
+                }
+            }
+        }
+
+        let event = match self.parser.parse_event(&line) {
+            Ok(e) => Ok(e.into_owned()),
+            Err(e) => {
+                eprintln!("error occurred on line {line}\n{e}");
+                Err(e)
+            }
+        };
+
+        Some(event)
+    }
+}
+
+// Parses events straight out of an in-memory log (e.g. a memory mapped file)
+// with every string borrowed from the buffer rather than copied
+pub struct BorrowedEventParser<'a> {
+    rest: &'a str,
+    parser: LineParser,
+}
+
+impl<'a> BorrowedEventParser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self::with_interner(input, Arc::new(Interner::new()))
+    }
+
+    pub fn with_interner(input: &'a str, interner: Arc<Interner>) -> Self {
+        Self {
+            rest: input,
+            parser: LineParser::new(interner),
+        }
+    }
+
+    // Chunks cut from the middle of a file never see the COMBAT_LOG_VERSION
+    // line so they have to be told which dialect to expect
+    pub fn with_dialect(self, dialect: Dialect) -> Self {
+        self.parser.set_dialect(dialect);
+        self
+    }
+
+    // The dialect in effect after the last line read so far
+    pub fn dialect(&self) -> Dialect {
+        self.parser.dialect()
+    }
+
+    pub fn symbols(&self) -> SymbolTable {
+        self.parser.symbols()
+    }
+}
+
+impl<'a> Iterator for BorrowedEventParser<'a> {
+    type Item = Result<ParsedEvent<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+
+            let (line, rest) = self.rest.split_once('\n').unwrap_or((self.rest, ""));
+            self.rest = rest;
+            if !line.trim().is_empty() {
+                break line;
+            }
+        };
+
+        let event = self.parser.parse_event(line);
+        if let Err(e) = &event {
+            eprintln!("error occurred on line {line}\n{e}");
+        }
+
+        Some(event)
+    }
+}
+
+#[derive(Debug)]
+pub struct LineParser {
+    symbols: SymbolCache,
+    dialect: Cell<Dialect>,
+}
+
+impl LineParser {
+    pub fn new(interner: Arc<Interner>) -> Self {
+        Self {
+            symbols: SymbolCache::new(interner),
+            dialect: Cell::new(Dialect::default()),
+        }
+    }
+
+    pub fn dialect(&self) -> Dialect {
+        self.dialect.get()
+    }
+
+    pub fn set_dialect(&self, dialect: Dialect) {
+        self.dialect.set(dialect);
     }
 
-    fn parse_event(&self, line: impl AsRef<str>) -> Result<ParsedEvent> {
-        let line = line.as_ref();
+    // Clients write a new header every time logging restarts (e.g. after a
+    // reload) so the dialect can change part way through a file
+    pub fn apply_header(&self, header: &LogVersionEvent<'_>) {
+        self.set_dialect(Dialect::new(
+            header.version,
+            &header.build,
+            header.advanced_log,
+        ));
+    }
+
+    pub fn symbols(&self) -> SymbolTable {
+        self.symbols.interner().table()
+    }
+
+    pub fn parse_event<'a>(&self, line: &'a str) -> Result<ParsedEvent<'a>> {
         let Some((ts, rest)) = line.split_once("  ") else {
             panic!("invalid event found (expected timestamp) - {line}");
         };
@@ -52,12 +202,25 @@ impl<R: BufRead> EventLogParser<R> {
         };
 
         let args = args.trim();
-        let event_type = EventType::try_from(event)?;
+        let Ok(event_type) = EventType::try_from(event) else {
+            return Ok(ParsedEvent {
+                timestamp,
+                event_type: EventType::Unknown(self.symbols.intern(event)),
+                event: Event::Unknown {
+                    name: Cow::Borrowed(event),
+                    raw_args: Cow::Borrowed(args),
+                },
+            });
+        };
+
         let event = match event_type {
-            EventType::CombatLogVersion => Event::LogVersion(
-                self.parse_header(args)
-                    .context("parsing combat log version")?,
-            ),
+            EventType::CombatLogVersion => {
+                let header = self
+                    .parse_header(args)
+                    .context("parsing combat log version")?;
+                self.apply_header(&header);
+                Event::LogVersion(header)
+            }
             EventType::ZoneChange => Event::ZoneChange(
                 self.parse_zone_change(args)
                     .context("parsing zone change")?,
@@ -68,6 +231,9 @@ impl<R: BufRead> EventLogParser<R> {
             EventType::EncounterStart | EventType::EncounterEnd => self
                 .parse_encounter_start_end(event_type, args)
                 .context("parsing encounter start / end")?,
+            EventType::ChallengeModeStart | EventType::ChallengeModeEnd => self
+                .parse_challenge_mode_start_end(event_type, args)
+                .context("parsing challenge mode start / end")?,
             EventType::ArenaMatchStart | EventType::ArenaMatchEnd => self
                 .parse_arena_start_end(event_type, args)
                 .context("parsing arena start / end")?,
@@ -78,13 +244,12 @@ impl<R: BufRead> EventLogParser<R> {
                 self.parse_stagger_event(event_type, args)
                     .context("parsing stagger")?,
             ),
-            EventType::CombatantInfo => {
-                Event::Combatant(Combatant::new(args).context("parsing combatant info")?)
-            }
+            EventType::CombatantInfo => Event::Combatant(
+                Combatant::new(args, &self.symbols).context("parsing combatant info")?,
+            ),
             EventType::Emote => Event::Emote(EmoteEvent),
             _ => Event::Combat(
                 self.parse_combat_event(event_type, args)
-                    .context(format!("processing {} event", event_type))
                     .with_context(|| format!("parsing combat event - {event_type}"))?,
             ),
         };
@@ -96,13 +261,13 @@ impl<R: BufRead> EventLogParser<R> {
         })
     }
 
-    fn parse_header(&self, args: &str) -> Result<LogVersionEvent> {
-        let mut arg_parser = EventArgParser::new(args, ',');
+    fn parse_header<'a>(&self, args: &'a str) -> Result<LogVersionEvent<'a>> {
+        let mut arg_parser = EventArgParser::new(args, ',', &self.symbols);
         let version = arg_parser.next_numeric::<u32>()?;
         arg_parser.next_string()?;
         let advanced_log = arg_parser.next_string()? == "1";
         arg_parser.next_string()?;
-        let build = arg_parser.next_string()?.to_string();
+        let build = Cow::Borrowed(arg_parser.next_string()?);
 
         Ok(LogVersionEvent {
             version,
@@ -111,10 +276,10 @@ impl<R: BufRead> EventLogParser<R> {
         })
     }
 
-    fn parse_zone_change(&self, args: &str) -> Result<ZoneChangeEvent> {
-        let mut parser = EventArgParser::new(args, ',');
+    fn parse_zone_change<'a>(&self, args: &'a str) -> Result<ZoneChangeEvent<'a>> {
+        let mut parser = EventArgParser::new(args, ',', &self.symbols);
         let instance_id = parser.next_numeric::<u32>()?;
-        let zone_name = parser.next_string()?.trim_matches('"').to_string();
+        let zone_name = Cow::Borrowed(parser.next_string()?.trim_matches('"'));
         let difficulty = parser.next_numeric::<u16>()?;
 
         Ok(ZoneChangeEvent {
@@ -124,10 +289,10 @@ impl<R: BufRead> EventLogParser<R> {
         })
     }
 
-    fn parse_map_change(&self, args: &str) -> Result<MapChangeEvent> {
-        let mut parser = EventArgParser::new(args, ',');
+    fn parse_map_change<'a>(&self, args: &'a str) -> Result<MapChangeEvent<'a>> {
+        let mut parser = EventArgParser::new(args, ',', &self.symbols);
         let map_id = parser.next_numeric::<u32>()?;
-        let map_name = parser.next_string()?.trim_matches('"').to_string();
+        let map_name = Cow::Borrowed(parser.next_string()?.trim_matches('"'));
         let x0 = parser.next_numeric::<f32>()?;
         let x1 = parser.next_numeric::<f32>()?;
         let y0 = parser.next_numeric::<f32>()?;
@@ -143,13 +308,17 @@ impl<R: BufRead> EventLogParser<R> {
         })
     }
 
-    fn parse_encounter_start_end(&self, event_type: EventType, args: &str) -> Result<Event> {
-        let mut parser = EventArgParser::new(args, ',');
+    fn parse_encounter_start_end<'a>(
+        &self,
+        event_type: EventType,
+        args: &'a str,
+    ) -> Result<Event<'a>> {
+        let mut parser = EventArgParser::new(args, ',', &self.symbols);
 
         match event_type {
             EventType::EncounterStart => {
                 let encounter_id = parser.next_numeric::<u32>()?;
-                let encounter_name = parser.next_string()?.to_string();
+                let encounter_name = Cow::Borrowed(parser.next_string()?);
                 let difficulty = Difficulty::from(parser.next_numeric::<u16>()?);
                 let group_size = parser.next_numeric::<u32>()?;
                 let instance_id = parser.next_numeric::<u32>()?;
@@ -164,7 +333,7 @@ impl<R: BufRead> EventLogParser<R> {
             }
             EventType::EncounterEnd => {
                 let encounter_id = parser.next_numeric::<u32>()?;
-                let encounter_name = parser.next_string()?.to_string();
+                let encounter_name = Cow::Borrowed(parser.next_string()?);
                 let difficulty = Difficulty::from(parser.next_numeric::<u16>()?);
                 let group_size = parser.next_numeric::<u32>()?;
                 let success = parser.next_numeric::<u8>()? == 1;
@@ -183,14 +352,60 @@ impl<R: BufRead> EventLogParser<R> {
         }
     }
 
-    fn parse_arena_start_end(&self, event_type: EventType, args: &str) -> Result<Event> {
-        let mut parser = EventArgParser::new(args, ',');
+    fn parse_challenge_mode_start_end<'a>(
+        &self,
+        event_type: EventType,
+        args: &'a str,
+    ) -> Result<Event<'a>> {
+        let mut parser = EventArgParser::new(args, ',', &self.symbols);
+
+        match event_type {
+            EventType::ChallengeModeStart => {
+                let zone_name = Cow::Borrowed(parser.next_string()?);
+                let instance_id = parser.next_numeric::<u32>()?;
+                let challenge_mode_id = parser.next_numeric::<u32>()?;
+                let keystone_level = parser.next_numeric::<u32>()?;
+                let affixes = parser
+                    .next()
+                    .split(',')
+                    .filter(|affix| !affix.is_empty())
+                    .map(|affix| affix.parse::<u32>())
+                    .collect::<Result<Vec<u32>, _>>()?;
+
+                Ok(Event::ChallengeModeStart(ChallengeModeStartEvent {
+                    zone_name,
+                    instance_id,
+                    challenge_mode_id,
+                    keystone_level,
+                    affixes,
+                }))
+            }
+            EventType::ChallengeModeEnd => {
+                // Newer clients append the old and new rating which we ignore
+                let instance_id = parser.next_numeric::<u32>()?;
+                let success = parser.next_numeric::<u8>()? == 1;
+                let keystone_level = parser.next_numeric::<u32>()?;
+                let total_time = parser.next_numeric::<u64>()?;
+
+                Ok(Event::ChallengeModeEnd(ChallengeModeEndEvent {
+                    instance_id,
+                    success,
+                    keystone_level,
+                    total_time,
+                }))
+            }
+            _ => unreachable!("checked in outer match"),
+        }
+    }
+
+    fn parse_arena_start_end<'a>(&self, event_type: EventType, args: &'a str) -> Result<Event<'a>> {
+        let mut parser = EventArgParser::new(args, ',', &self.symbols);
 
         match event_type {
             EventType::ArenaMatchStart => {
                 let instance_id = parser.next_numeric::<u32>()?;
                 let unk = parser.next_numeric::<u32>()?;
-                let match_type = parser.next_string()?.to_string();
+                let match_type = Cow::Borrowed(parser.next_string()?);
                 let team_id = parser.next_numeric::<u32>()?;
 
                 Ok(Event::ArenaStart(ArenaStartEvent {
@@ -201,7 +416,7 @@ impl<R: BufRead> EventLogParser<R> {
                 }))
             }
             EventType::ArenaMatchEnd => {
-                let winning_team = parser.next_boolean();
+                let winning_team = parser.next_numeric::<u32>()?;
                 let match_duration = parser.next_numeric::<u32>()?;
                 let new_rating_team_one = parser.next_numeric::<u32>()?;
                 let new_rating_team_two = parser.next_numeric::<u32>()?;
@@ -217,16 +432,16 @@ impl<R: BufRead> EventLogParser<R> {
         }
     }
 
-    fn parse_world_marker_placed_removed(
+    fn parse_world_marker_placed_removed<'a>(
         &self,
         event_type: EventType,
-        args: &str,
-    ) -> Result<Event> {
-        let mut parser = EventArgParser::new(args, ',');
+        args: &'a str,
+    ) -> Result<Event<'a>> {
+        let mut parser = EventArgParser::new(args, ',', &self.symbols);
         match event_type {
             EventType::WorldMarkerPlaced => {
                 let instance_id = parser.next_numeric::<u32>()?;
-                let marker = RaidFlag::try_from(parser.next_numeric::<u32>()?)?;
+                let marker = RaidFlag::from(parser.next_numeric::<u32>()?);
                 let x = parser.next_numeric::<f32>()?;
                 let y = parser.next_numeric::<f32>()?;
 
@@ -238,7 +453,7 @@ impl<R: BufRead> EventLogParser<R> {
                 }))
             }
             EventType::WorldMarkerRemoved => {
-                let raid_flag = RaidFlag::try_from(parser.next_numeric::<u32>()?)?;
+                let raid_flag = RaidFlag::from(parser.next_numeric::<u32>()?);
                 Ok(Event::WorldMarkerRemoved(raid_flag))
             }
             _ => unreachable!("caught by outer match arm"),
@@ -246,8 +461,8 @@ impl<R: BufRead> EventLogParser<R> {
     }
 
     fn parse_stagger_event(&self, event_type: EventType, args: &str) -> Result<StaggerEvent> {
-        let mut parser = EventArgParser::new(args, ',');
-        let guid = Guid(parser.next_string()?.to_string());
+        let mut parser = EventArgParser::new(args, ',', &self.symbols);
+        let guid = Guid(self.symbols.intern(parser.next_string()?));
         let (spell_id, amount): (Option<u32>, f32) = if event_type == EventType::StaggerPrevented {
             let spell_id = parser.next_numeric::<u32>()?;
             let amount = parser.next_numeric::<f32>()?;
@@ -263,8 +478,12 @@ impl<R: BufRead> EventLogParser<R> {
         })
     }
 
-    fn parse_combat_event(&self, event_type: EventType, args: &str) -> Result<CombatEvent> {
-        let mut parser = EventArgParser::new(args, ',');
+    fn parse_combat_event<'a>(
+        &self,
+        event_type: EventType,
+        args: &'a str,
+    ) -> Result<CombatEvent<'a>> {
+        let mut parser = EventArgParser::new(args, ',', &self.symbols);
         let src = parser.target()?;
         let dst = parser.target()?;
 
@@ -274,10 +493,11 @@ impl<R: BufRead> EventLogParser<R> {
             None
         };
 
-        let adv = if event_type.has_advanced_parameters() {
-            Some(parser.advanced_parameters()?)
-        } else {
-            None
+        let adv = match self.dialect.get().layout() {
+            Some(layout) if event_type.has_advanced_parameters() => {
+                Some(parser.advanced_parameters(layout)?)
+            }
+            _ => None,
         };
 
         let environmental = if event_type == EventType::EnvironmentalDamage {
@@ -298,20 +518,26 @@ impl<R: BufRead> EventLogParser<R> {
         })
     }
 
-    fn parse_combat_suffix(
+    fn parse_combat_suffix<'a>(
         &self,
         event_type: EventType,
-        parser: &mut EventArgParser,
-    ) -> Result<Option<Suffix>> {
+        parser: &mut EventArgParser<'a, '_>,
+    ) -> Result<Option<Suffix<'a>>> {
         let suffix = match event_type {
             EventType::SwingDamage
+            | EventType::SwingDamageLanded
             | EventType::SpellDamage
             | EventType::RangeDamage
             | EventType::SpellPeriodicDamage
+            | EventType::SpellDamageSupport
+            | EventType::RangeDamageSupport
+            | EventType::SwingDamageLandedSupport
             | EventType::SpellPeriodicDamageSupport
             | EventType::DamageSplit
             | EventType::DamageShield
-            | EventType::EnvironmentalDamage => Some(Suffix::Damage(parser.damage()?)),
+            | EventType::EnvironmentalDamage => Some(Suffix::Damage(
+                parser.damage(self.dialect.get(), event_type)?,
+            )),
             // TMP
             EventType::SwingMissed
             | EventType::SpellMissed
@@ -323,7 +549,10 @@ impl<R: BufRead> EventLogParser<R> {
             }
             EventType::SpellHeal
             | EventType::SpellPeriodicHeal
-            | EventType::SpellPeriodicHealSupport => Some(Suffix::Heal(parser.heal()?)),
+            | EventType::SpellHealSupport
+            | EventType::SpellPeriodicHealSupport => {
+                Some(Suffix::Heal(parser.heal(self.dialect.get(), event_type)?))
+            }
             EventType::SpellHealAbsorbed => Some(Suffix::HealAbsorbed(parser.heal_absorb()?)),
             EventType::SpellCastFailed => Some(Suffix::Fail(parser.fail()?)),
             EventType::SpellEnergize | EventType::SpellPeriodicEnergize => {
@@ -346,9 +575,7 @@ impl<R: BufRead> EventLogParser<R> {
             | EventType::SpellAuraAppliedDose
             | EventType::SpellAuraRemovedDose
             | EventType::SpellAuraRefresh => Some(Suffix::Aura(parser.aura()?)),
-            EventType::SpellAuraBroken => Some(Suffix::AuraBroken(AuraType::try_from(
-                parser.next_string()?,
-            )?)),
+            EventType::SpellAuraBroken => Some(Suffix::AuraBroken(parser.aura_type()?)),
             EventType::SpellAuraBrokenSpell => Some(Suffix::AuraBrokenSpell(parser.aura_spell()?)),
             EventType::SpellEmpowerInterrupt | EventType::SpellEmpowerEnd => {
                 Some(Suffix::Empower(parser.next_numeric::<u32>()?))
@@ -356,6 +583,25 @@ impl<R: BufRead> EventLogParser<R> {
             EventType::EnchantRemoved | EventType::EnchantApplied => {
                 Some(Suffix::Enchant(parser.enchant()?))
             }
+            // Older clients don't write the unconscious on death flag
+            EventType::UnitDied => Some(Suffix::UnitDied(
+                parser.next_numeric::<u32>().unwrap_or_default(),
+            )),
+            EventType::UnitDestroyed => Some(Suffix::UnitDestroyed(
+                parser.next_numeric::<u32>().unwrap_or_default(),
+            )),
+            EventType::UnitDissipates => Some(Suffix::UnitDissipates(
+                parser.next_numeric::<u32>().unwrap_or_default(),
+            )),
+            EventType::PartyKill => Some(Suffix::PartyKill(
+                parser.next_numeric::<u32>().unwrap_or_default(),
+            )),
+            EventType::SpellSummon => Some(Suffix::Summon),
+            EventType::SpellCreate => Some(Suffix::Create),
+            EventType::SpellResurrect => Some(Suffix::Resurrect),
+            EventType::SpellInstakill => Some(Suffix::Instakill(
+                parser.next_numeric::<u32>().unwrap_or_default(),
+            )),
             _ => None,
         };
 
@@ -363,48 +609,24 @@ impl<R: BufRead> EventLogParser<R> {
     }
 }
 
-impl<R: BufRead> Iterator for EventLogParser<R> {
-    type Item = Result<ParsedEvent>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut line = String::new();
-        match self.reader.read_line(&mut line) {
-            Ok(0) => return None,
-            Ok(_) => {}
-            Err(e) => {
-                eprintln!("error occurred parsing line - {}", e.to_string());
-                return None;
-            }
-        }
-
-        let event = match self.parse_event(&line) {
-            Ok(e) => Ok(e),
-            Err(e) => {
-                eprintln!("error occurred on line {line}\n{}", e.to_string());
-                Err(e)
-            }
-        };
-
-        Some(event)
-    }
-}
-
-pub struct EventArgParser<'a> {
+pub struct EventArgParser<'a, 's> {
     rest: &'a str,
     delimiter: char,
+    symbols: &'s SymbolCache,
 }
 
-impl<'a> EventArgParser<'a> {
-    pub fn new(input: &'a str, delim: char) -> Self {
+impl<'a, 's> EventArgParser<'a, 's> {
+    pub fn new(input: &'a str, delim: char, symbols: &'s SymbolCache) -> Self {
         Self {
             rest: input,
             delimiter: delim,
+            symbols,
         }
     }
 
     pub fn target(&mut self) -> Result<Target> {
-        let guid = Guid(self.next_string()?.to_string());
-        let name = self.next_string()?.trim_matches('"').to_string();
+        let guid = Guid(self.symbols.intern(self.next_string()?));
+        let name = self.symbols.intern(self.next_string()?.trim_matches('"'));
         let unit_flags = self.next_numeric::<u32>()?;
         let raid_flags = self.next_numeric::<u32>()?;
 
@@ -412,14 +634,14 @@ impl<'a> EventArgParser<'a> {
             guid,
             name,
             unit_flags: UnitFlags::new(unit_flags)?,
-            raid_flags: RaidFlag::try_from(raid_flags & 0xFF)?,
+            raid_flags: RaidFlag::from(raid_flags & 0xFF),
         })
     }
 
     pub fn spell_parameters(&mut self) -> Result<SpellParameters> {
         let spell_id = self.next_numeric::<u32>()?;
-        let spell_name = self.next_string()?.trim_matches('"').to_string();
-        let spell_school = SpellSchool::try_from(self.next_numeric::<u8>()?)?;
+        let spell_name = self.symbols.intern(self.next_string()?.trim_matches('"'));
+        let spell_school = SpellSchool::from(self.next_numeric::<u8>()?);
 
         Ok(SpellParameters {
             spell_id,
@@ -428,26 +650,40 @@ impl<'a> EventArgParser<'a> {
         })
     }
 
-    pub fn advanced_parameters(&mut self) -> Result<AdvancedParameters> {
-        let info = Guid(self.next_string()?.to_string());
-        let owner = Guid(self.next_string()?.to_string());
+    pub fn advanced_parameters(&mut self, layout: AdvancedLayout) -> Result<AdvancedParameters> {
+        let raw_info = self.next_string()?;
+        let is_player = raw_info.starts_with("Player-");
+        let info = Guid(self.symbols.intern(raw_info));
+        let owner = Guid(self.symbols.intern(self.next_string()?));
         let current_hp = self.next_numeric::<i32>()?;
         let max_hp = self.next_numeric::<u32>()?;
         let attack_power = self.next_numeric::<u32>()?;
         let spell_power = self.next_numeric::<u32>()?;
         let armor = self.next_numeric::<i32>()?;
 
-        // No idea what these are -- clarify
-        let _ = self.next_numeric::<u32>()?;
-        let _ = self.next_numeric::<u32>()?;
+        // The field after support power has not been identified yet so it is
+        // skipped rather than exposed under a made up name
+        let support_power = match layout {
+            AdvancedLayout::Extended => {
+                let support_power = self.next_numeric::<u32>()?;
+                self.next_numeric::<u32>()?;
+                Some(support_power)
+            }
+            AdvancedLayout::Classic | AdvancedLayout::Retail => None,
+        };
 
         let absorb = self.next_numeric::<u32>()?;
 
+        // Reading a line with the wrong dialect lands here with garbage so
+        // this has to be an error rather than a panic
         let power_type = MultiValue(
             self.multi_value()?
                 .into_iter()
-                .map(|v| PowerType::try_from(v as u8).expect("power_type value in range of u8"))
-                .collect(),
+                .map(|v| {
+                    let v = u8::try_from(v).map_err(|_| eyre!("power type out of range: {v}"))?;
+                    Ok(PowerType::from(v))
+                })
+                .collect::<Result<Vec<_>>>()?,
         );
         let current_power = MultiValue(self.multi_value()?);
         let max_power = MultiValue(self.multi_value()?);
@@ -458,6 +694,7 @@ impl<'a> EventArgParser<'a> {
         let map_id = self.next_numeric::<u32>()?;
         let facing = self.next_numeric::<f32>()?;
         let level = self.next_numeric::<u32>()?;
+        let item_level = (is_player && layout.reports_item_level()).then_some(level);
 
         Ok(AdvancedParameters {
             info,
@@ -467,6 +704,7 @@ impl<'a> EventArgParser<'a> {
             attack_power,
             spell_power,
             armor,
+            support_power,
             absorb,
             power_type,
             current_power,
@@ -477,13 +715,14 @@ impl<'a> EventArgParser<'a> {
             map_id,
             facing,
             level,
+            item_level,
         })
     }
 
-    pub fn enchant(&mut self) -> Result<EnchantEvent> {
-        let name = self.next_string()?.to_string();
+    pub fn enchant(&mut self) -> Result<EnchantEvent<'a>> {
+        let name = Cow::Borrowed(self.next_string()?);
         let item_id = self.next_numeric::<u32>()?;
-        let item_name = self.next_string()?.to_string();
+        let item_name = Cow::Borrowed(self.next_string()?);
 
         Ok(EnchantEvent {
             name,
@@ -493,7 +732,7 @@ impl<'a> EventArgParser<'a> {
     }
 
     pub fn aura(&mut self) -> Result<AuraEvent> {
-        let aura = AuraType::try_from(self.next_string()?)?;
+        let aura = self.aura_type()?;
         let amount = if !self.is_empty() {
             Some(self.next_numeric::<u32>()?)
         } else {
@@ -505,40 +744,55 @@ impl<'a> EventArgParser<'a> {
 
     pub fn aura_spell(&mut self) -> Result<AuraWithSpellEvent> {
         let spell = self.spell_parameters()?;
-        let aura = AuraType::try_from(self.next_string()?)?;
+        let aura = self.aura_type()?;
 
         Ok(AuraWithSpellEvent { spell, aura })
     }
 
+    // Types the client adds later are kept by name rather than failing the line
+    pub fn aura_type(&mut self) -> Result<AuraType> {
+        let value = self.next_string()?;
+        Ok(AuraType::try_from(value)
+            .unwrap_or_else(|_| AuraType::Unknown(self.symbols.intern(value))))
+    }
+
     pub fn environmental(&mut self) -> Result<EnvironmentalType> {
-        EnvironmentalType::try_from(self.next_string()?)
+        let value = self.next_string()?;
+        Ok(EnvironmentalType::try_from(value)
+            .unwrap_or_else(|_| EnvironmentalType::Unknown(self.symbols.intern(value))))
     }
 
     pub fn multi_value(&mut self) -> Result<Vec<u32>> {
         let value = self.next_string()?;
-        Ok(value
+        value
             .split('|')
             .map(|s| {
                 let v = s
                     .parse::<i32>()
-                    .expect(&format!("a valid numeric value for multi-value - {s}"));
-                if v < 0 { 0 } else { v as u32 }
+                    .map_err(|_| eyre!("invalid numeric value for multi-value - {s}"))?;
+                Ok(if v < 0 { 0 } else { v as u32 })
             })
-            .collect())
+            .collect()
     }
 
-    pub fn damage(&mut self) -> Result<DamageEvent> {
+    pub fn damage(&mut self, dialect: Dialect, event_type: EventType) -> Result<DamageEvent> {
         let amount = self.next_numeric::<u32>()?;
-        let base_amount = self.next_numeric::<u32>()?;
+        let base_amount = if dialect.has_base_amount() {
+            Some(self.next_numeric::<u32>()?)
+        } else {
+            None
+        };
         let overkill = self.next_numeric::<i32>()?;
         let overkill = if overkill < 0 { 0 } else { overkill as u32 };
-        let school = SpellSchool::try_from(self.next_numeric::<u8>()?)?;
+        let school = SpellSchool::from(self.next_numeric::<u8>()?);
         let resisted = self.next_numeric::<u32>()?;
         let blocked = self.next_numeric::<u32>()?;
         let absorbed = self.next_numeric::<i32>()?;
         let critical = self.next_boolean();
         let glancing = self.next_boolean();
         let crushing = self.next_boolean();
+        let is_offhand = self.next_boolean();
+        let supporter = self.supporter(event_type)?;
 
         Ok(DamageEvent {
             amount,
@@ -551,11 +805,25 @@ impl<'a> EventArgParser<'a> {
             critical,
             glancing,
             crushing,
+            is_offhand,
+            supporter,
         })
     }
 
+    // Support events end with the Augmentation Evoker whose buff contributed
+    // to the amount
+    fn supporter(&mut self, event_type: EventType) -> Result<Option<Guid>> {
+        if event_type.is_support() {
+            Ok(Some(Guid(self.symbols.intern(self.next_string()?))))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn missed(&mut self, event_type: EventType) -> Result<MissEvent> {
-        let miss_type = MissType::try_from(self.next_string()?)?;
+        let value = self.next_string()?;
+        let miss_type = MissType::try_from(value)
+            .unwrap_or_else(|_| MissType::Unknown(self.symbols.intern(value)));
         let is_offhand = self.next_boolean();
         let (amount, base_amount, critical) = match miss_type {
             MissType::Block | MissType::Resist => {
@@ -594,7 +862,7 @@ impl<'a> EventArgParser<'a> {
             .next()
             .expect("valid character for absorb event");
 
-        let src_spell = if next.is_digit(10) {
+        let src_spell = if next.is_ascii_digit() {
             Some(self.spell_parameters()?)
         } else {
             None
@@ -605,11 +873,7 @@ impl<'a> EventArgParser<'a> {
         let amount = self.next_numeric::<i32>()?;
         let total_amount = self.next_numeric::<u32>()?;
         let critical = self.next_boolean();
-        let target = if event_type == EventType::SpellAbsorbedSupport {
-            Some(Guid(self.next_string()?.to_string()))
-        } else {
-            None
-        };
+        let supporter = self.supporter(event_type)?;
 
         Ok(AbsorbEvent {
             src_spell,
@@ -618,16 +882,21 @@ impl<'a> EventArgParser<'a> {
             amount,
             total_amount,
             critical,
-            target,
+            supporter,
         })
     }
 
-    pub fn heal(&mut self) -> Result<HealEvent> {
+    pub fn heal(&mut self, dialect: Dialect, event_type: EventType) -> Result<HealEvent> {
         let amount = self.next_numeric::<u32>()?;
-        let base_amount = self.next_numeric::<u32>()?;
+        let base_amount = if dialect.has_base_amount() {
+            Some(self.next_numeric::<u32>()?)
+        } else {
+            None
+        };
         let overhealing = self.next_numeric::<u32>()?;
         let absorbed = self.next_numeric::<u32>()?;
         let critical = self.next_boolean();
+        let supporter = self.supporter(event_type)?;
 
         Ok(HealEvent {
             amount,
@@ -635,6 +904,7 @@ impl<'a> EventArgParser<'a> {
             overhealing,
             absorbed,
             critical,
+            supporter,
         })
     }
 
@@ -652,16 +922,16 @@ impl<'a> EventArgParser<'a> {
         })
     }
 
-    pub fn fail(&mut self) -> Result<FailEvent> {
+    pub fn fail(&mut self) -> Result<FailEvent<'a>> {
         Ok(FailEvent {
-            msg: self.next_string()?.to_string(),
+            msg: Cow::Borrowed(self.next_string()?),
         })
     }
 
     pub fn energise(&mut self) -> Result<EnergizeEvent> {
         let amount = self.next_numeric::<f32>()?;
         let over_energize = self.next_numeric::<f32>()?;
-        let power = PowerType::try_from(self.next_numeric::<u8>()?)?;
+        let power = PowerType::from(self.next_numeric::<u8>()?);
         let max = self.next_numeric::<u32>()?;
 
         Ok(EnergizeEvent {
@@ -674,7 +944,7 @@ impl<'a> EventArgParser<'a> {
 
     pub fn drain(&mut self, is_leech: bool) -> Result<DrainEvent> {
         let amount = self.next_numeric::<u32>()?;
-        let power = PowerType::try_from(self.next_numeric::<u8>()?)?;
+        let power = PowerType::from(self.next_numeric::<u8>()?);
         let extra_amount = self.next_numeric::<u32>()?;
 
         let max = if is_leech {
@@ -698,7 +968,7 @@ impl<'a> EventArgParser<'a> {
 
     pub fn steal_with_aura(&mut self) -> Result<StealWithAuraEvent> {
         let spell = self.spell_parameters()?;
-        let aura = AuraType::try_from(self.next_string()?)?;
+        let aura = self.aura_type()?;
 
         Ok(StealWithAuraEvent { spell, aura })
     }
@@ -723,10 +993,8 @@ impl<'a> EventArgParser<'a> {
                 }
                 '(' => stack.push(')'),
                 '[' => stack.push(']'),
-                ')' | ']' => {
-                    if stack.last() == Some(&ch) {
-                        stack.pop();
-                    }
+                ')' | ']' if stack.last() == Some(&ch) => {
+                    stack.pop();
                 }
                 ch if ch == self.delimiter && stack.is_empty() => {
                     end = i;
@@ -740,7 +1008,7 @@ impl<'a> EventArgParser<'a> {
     }
 }
 
-impl<'a> EventParser<'a> for EventArgParser<'a> {
+impl<'a> EventParser<'a> for EventArgParser<'a, '_> {
     fn next(&mut self) -> &'a str {
         let mut end = self.rest.len();
         let mut new_start = self.rest.len();
@@ -758,10 +1026,8 @@ impl<'a> EventParser<'a> for EventArgParser<'a> {
                 }
                 '(' => stack.push(')'),
                 '[' => stack.push(']'),
-                ')' | ']' => {
-                    if stack.last() == Some(&ch) {
-                        stack.pop();
-                    }
+                ')' | ']' if stack.last() == Some(&ch) => {
+                    stack.pop();
                 }
                 ch if ch == self.delimiter && stack.is_empty() => {
                     end = i;
@@ -819,3 +1085,253 @@ pub trait EventParser<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod parser_tests {
+    use super::*;
+
+    const LOG: &str = "4/19/2026 20:00:01.000  SPELL_AURA_APPLIED,Player-1-0001,\"Tank-Realm\",0x511,0x0,Player-1-0001,\"Tank-Realm\",0x511,0x0,6673,\"Battle Shout\",0x1,BUFF\n\
+        4/19/2026 20:00:02.000  ZONE_CHANGE,2769,\"Liberation of Undermine\",16\n";
+
+    const CAST: &str = "SPELL_CAST_SUCCESS,Player-1-0001,\"Tank-Realm\",0x511,0x0,0000000000000000,nil,0x80000000,0x80000000,6673,\"Battle Shout\",0x1";
+
+    fn header(version: u32) -> String {
+        dialect_header(version, 1, "11.1.5")
+    }
+
+    fn dialect_header(version: u32, advanced: u8, build: &str) -> String {
+        format!(
+            "4/19/2026 20:00:00.000  COMBAT_LOG_VERSION,{version},ADVANCED_LOG_ENABLED,{advanced},BUILD_VERSION,{build},PROJECT_ID,1\n"
+        )
+    }
+
+    fn damage(log: &str) -> Result<CombatEvent<'_>> {
+        let events = BorrowedEventParser::new(log).collect::<Result<Vec<ParsedEvent>>>()?;
+        match events.into_iter().last().expect("a damage event").event {
+            Event::Combat(combat) => Ok(combat),
+            _ => panic!("expected a combat event"),
+        }
+    }
+
+    fn advanced(log: &str) -> Result<AdvancedParameters> {
+        let events = BorrowedEventParser::new(log).collect::<Result<Vec<ParsedEvent>>>()?;
+        match &events.last().expect("a cast event").event {
+            Event::Combat(combat) => Ok(combat.adv.clone().expect("advanced parameters")),
+            _ => panic!("expected a combat event"),
+        }
+    }
+
+    #[test]
+    fn it_parses_the_short_advanced_layout() -> Result<()> {
+        let cast = format!(
+            "4/19/2026 20:00:01.000  {CAST},Player-1-0001,0000000000000000,100,200,300,400,500,50,0,100,100,0,-10.5,20.25,2769,1.5,639\n"
+        );
+
+        let classic = advanced(&format!("{}{cast}", header(9)))?;
+        assert_eq!(classic.armor, 500);
+        assert_eq!(classic.support_power, None);
+        assert_eq!(classic.absorb, 50);
+        assert_eq!(classic.map_id, 2769);
+        assert_eq!(classic.level, 639);
+        assert_eq!(classic.item_level, None);
+
+        let retail = advanced(&format!("{}{cast}", header(20)))?;
+        assert_eq!(retail.absorb, 50);
+        assert_eq!(retail.item_level, Some(639));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_parses_the_extended_advanced_layout() -> Result<()> {
+        let cast = format!(
+            "4/19/2026 20:00:01.000  {CAST},Player-1-0001,0000000000000000,100,200,300,400,500,7,8,50,0,100,100,0,-10.5,20.25,2769,1.5,639\n"
+        );
+
+        for log in [format!("{}{cast}", header(22)), cast.clone()] {
+            let adv = advanced(&log)?;
+            assert_eq!(adv.armor, 500);
+            assert_eq!(adv.support_power, Some(7));
+            assert_eq!(adv.absorb, 50);
+            assert_eq!(adv.facing, 1.5);
+            assert_eq!(adv.item_level, Some(639));
+        }
+
+        Ok(())
+    }
+
+    const HIT: &str = "SPELL_DAMAGE,Player-1-0001,\"Tank-Realm\",0x511,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,6343,\"Thunder Clap\",0x1";
+    const SHORT_ADV: &str =
+        "Creature-0-1-2-3-4-5,0000000000000000,100,200,0,0,500,0,1,0,0,0,-10.5,20.25,1429,1.5,63";
+
+    #[test]
+    fn classic_era_damage_has_no_base_amount() -> Result<()> {
+        let log = format!(
+            "{}4/19/2026 20:00:01.000  {HIT},{SHORT_ADV},1200,-1,1,0,0,0,1,nil,nil\n",
+            dialect_header(9, 1, "1.15.7")
+        );
+
+        let combat = damage(&log)?;
+        let adv = combat.adv.expect("advanced parameters");
+        assert_eq!(adv.level, 63);
+        assert_eq!(adv.item_level, None);
+        let Some(Suffix::Damage(hit)) = combat.suffix else {
+            panic!("expected a damage suffix");
+        };
+        assert_eq!(hit.amount, 1200);
+        assert_eq!(hit.base_amount, None);
+        assert_eq!(hit.overkill, 0);
+        assert!(hit.critical);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cataclysm_classic_damage_has_a_base_amount() -> Result<()> {
+        let log = format!(
+            "{}4/19/2026 20:00:01.000  {HIT},{SHORT_ADV},1200,1000,-1,1,0,0,0,nil,nil,nil\n",
+            dialect_header(20, 1, "4.4.2")
+        );
+
+        let combat = damage(&log)?;
+        assert_eq!(combat.adv.map(|adv| adv.item_level), Some(None));
+        let Some(Suffix::Damage(hit)) = combat.suffix else {
+            panic!("expected a damage suffix");
+        };
+        assert_eq!(hit.base_amount, Some(1000));
+
+        Ok(())
+    }
+
+    #[test]
+    fn unadvanced_logs_have_no_advanced_parameters() -> Result<()> {
+        let log = format!(
+            "{}4/19/2026 20:00:01.000  {HIT},1200,1000,-1,1,0,0,0,nil,nil,nil\n",
+            dialect_header(22, 0, "11.1.5")
+        );
+
+        let combat = damage(&log)?;
+        assert_eq!(combat.adv, None);
+        let Some(Suffix::Damage(hit)) = combat.suffix else {
+            panic!("expected a damage suffix");
+        };
+        assert_eq!(hit.amount, 1200);
+        assert_eq!(hit.base_amount, Some(1000));
+
+        Ok(())
+    }
+
+    #[test]
+    fn lines_in_the_other_dialect_are_an_error() {
+        let swing = "4/19/2026 20:00:01.000  SWING_DAMAGE,Player-1-0001,\"Tank-Realm\",0x511,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,1200,1000,-1,1,0,0,0,1,nil,nil,nil\n";
+        let mut parser =
+            BorrowedEventParser::new(swing).with_dialect(Dialect::new(20, "10.2.0", true));
+        assert!(parser.next().is_some_and(|event| event.is_err()));
+    }
+
+    #[test]
+    fn summons_and_instakills_have_typed_accessors() -> Result<()> {
+        let log = "4/19/2026 20:00:01.000  SPELL_SUMMON,Player-1-0001,\"Hunter-Realm\",0x511,0x0,Creature-0-1-2-3-4-5,\"Wolf\",0xa28,0x0,883,\"Call Pet 1\",0x1\n\
+            4/19/2026 20:00:02.000  SPELL_INSTAKILL,Creature-0-1-2-3-4-6,\"Boss\",0xa48,0x0,Player-1-0001,\"Hunter-Realm\",0x511,0x0,1215589,\"Obliterate\",0x20,0\n";
+        let mut parser = BorrowedEventParser::new(log);
+        let events = parser.by_ref().collect::<Result<Vec<ParsedEvent>>>()?;
+        let symbols = parser.symbols();
+
+        let Event::Combat(summon) = &events[0].event else {
+            panic!("expected a combat event");
+        };
+        let summon = summon.summon().expect("a summon");
+        assert_eq!(symbols.resolve(summon.owner.name), "Hunter-Realm");
+        assert_eq!(symbols.resolve(summon.pet.name), "Wolf");
+        assert_eq!(summon.spell.spell_id, 883);
+
+        let Event::Combat(kill) = &events[1].event else {
+            panic!("expected a combat event");
+        };
+        assert_eq!(kill.summon(), None);
+        let kill = kill.instakill().expect("an instakill");
+        assert_eq!(symbols.resolve(kill.victim.name), "Hunter-Realm");
+        assert_eq!(kill.unconscious_on_death, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn borrowed_events_reference_the_input() -> Result<()> {
+        let mut parser = BorrowedEventParser::new(LOG);
+        let events = parser.by_ref().collect::<Result<Vec<ParsedEvent>>>()?;
+        let symbols = parser.symbols();
+        assert_eq!(events.len(), 2);
+
+        let Event::Combat(combat) = &events[0].event else {
+            panic!("expected a combat event");
+        };
+        let src = combat.src.expect("a source");
+        let dst = combat.dst.expect("a destination");
+        assert_eq!(src, dst);
+        assert_eq!(symbols.resolve(src.guid), "Player-1-0001");
+        assert_eq!(symbols.resolve(src.name), "Tank-Realm");
+        assert_eq!(
+            combat.spell.map(|s| symbols.resolve(s.spell_name)),
+            Some("Battle Shout")
+        );
+
+        let Event::ZoneChange(zone) = &events[1].event else {
+            panic!("expected a zone change");
+        };
+        assert!(matches!(
+            zone.zone_name,
+            Cow::Borrowed("Liberation of Undermine")
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn owned_events_match_borrowed_ones() -> Result<()> {
+        let interner = Arc::new(Interner::new());
+        let borrowed = BorrowedEventParser::with_interner(LOG, interner.clone())
+            .collect::<Result<Vec<ParsedEvent>>>()?;
+        let owned = EventLogParser::with_interner(LOG.as_bytes(), interner)
+            .collect::<Result<Vec<ParsedEvent>>>()?;
+        assert_eq!(borrowed, owned);
+
+        let converted = borrowed
+            .into_iter()
+            .map(ParsedEvent::into_owned)
+            .collect::<Vec<ParsedEvent<'static>>>();
+        assert_eq!(converted, owned);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_values_are_kept_instead_of_failing() -> Result<()> {
+        let log = "4/19/2026 20:00:00.000  SPELL_FUTURE_THING,Player-1-0001,42,nil\n\
+            4/19/2026 20:00:01.000  SWING_MISSED,Player-1-0001,\"Rogue-Realm\",0x511,0x0,Creature-0-1-2-3-4-5,\"Boss\",0xa48,0x0,PHASE,nil\n";
+        let parser = BorrowedEventParser::new(log);
+        let events = parser.collect::<Result<Vec<ParsedEvent>>>()?;
+        assert_eq!(events.len(), 2);
+
+        assert!(matches!(events[0].event_type, EventType::Unknown(_)));
+        assert!(matches!(
+            &events[0].event,
+            Event::Unknown { name, raw_args }
+                if name == "SPELL_FUTURE_THING" && raw_args == "Player-1-0001,42,nil"
+        ));
+
+        let Event::Combat(combat) = &events[1].event else {
+            panic!("expected a combat event");
+        };
+        assert!(matches!(
+            &combat.suffix,
+            Some(Suffix::Missed(miss)) if matches!(miss.miss_type, MissType::Unknown(_))
+        ));
+
+        assert_eq!(PowerType::from(250), PowerType::Unknown(250));
+        assert_eq!(RaidFlag::from(3), RaidFlag::Unknown(3));
+        assert_eq!(RaidFlag::from(0x80000000), RaidFlag::None);
+
+        Ok(())
+    }
+}
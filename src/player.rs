@@ -1,17 +1,18 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr};
 
 use crate::{
+    intern::SymbolCache,
     parser::EventParser,
-    types::{Faction, Guid, Specialization},
+    types::{EquipmentSlot, Faction, Guid, PrimaryStat, Specialization},
 };
-use eyre::{Context, Result};
+use eyre::{Context, Result, eyre};
 use num::Num;
 
 pub type Gem = (u32, u32);
 pub type Enchantment = (u32, u32, u32);
 pub type PvpTalents = (u32, u32, u32, u32);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Combatant {
     pub guid: Guid,
     pub faction: Faction,
@@ -25,9 +26,9 @@ pub struct Combatant {
 }
 
 impl Combatant {
-    pub fn new(args: &str) -> Result<Self> {
+    pub fn new(args: &str, symbols: &SymbolCache) -> Result<Self> {
         let mut root_parser = CombatantParser::new(args);
-        let guid = Guid(root_parser.next_string()?.to_string());
+        let guid = Guid(symbols.intern(root_parser.next_string()?));
         let faction = if root_parser.next_numeric::<u8>()? == 0 {
             Faction::Horde
         } else {
@@ -35,23 +36,22 @@ impl Combatant {
         };
 
         let stats = Stats::new(&mut root_parser).context("parsing combatant stats")?;
-        let spec = Specialization::try_from(
+        let spec = Specialization::from(
             root_parser
                 .next_numeric::<u16>()
                 .context("parsing spec id")?,
-        )
-        .context("parsing specialisation")?;
+        );
 
-        let talent_str = root_parser.next();
-        let talents = parse_talents(&talent_str)?;
+        let talent_str = root_parser.next_group()?;
+        let talents = parse_talents(talent_str)?;
 
-        let pvp_talent_str = root_parser.next();
-        let pvp_talents = parse_pvp_talents(&pvp_talent_str)?;
+        let pvp_talent_str = root_parser.next_group()?;
+        let pvp_talents = parse_pvp_talents(pvp_talent_str)?;
 
-        let equipment_str = root_parser.next();
+        let equipment_str = root_parser.next_group()?;
         let equipment = parse_equipment(equipment_str)?;
 
-        let auras = parse_tracked_auras(root_parser.next())?;
+        let auras = parse_tracked_auras(root_parser.next_group()?, symbols)?;
         let pvp_stats = PvpStats::new(&mut root_parser)?;
 
         Ok(Combatant {
@@ -66,22 +66,43 @@ impl Combatant {
             pvp_stats,
         })
     }
+
+    // Every slot, with `None` for anything not equipped
+    pub fn gear(&self) -> BTreeMap<EquipmentSlot, Option<&Equipment>> {
+        EquipmentSlot::ALL
+            .into_iter()
+            .map(|slot| (slot, self.item(slot)))
+            .collect()
+    }
+
+    pub fn item(&self, slot: EquipmentSlot) -> Option<&Equipment> {
+        self.equipment
+            .get(slot.index())
+            .filter(|item| !item.is_empty())
+    }
+
+    pub fn primary_stat(&self) -> Option<(PrimaryStat, u32)> {
+        let stat = self.spec.primary_stat()?;
+        Some((stat, self.stats.primary(stat)))
+    }
 }
 
 fn parse_talents(talent_str: &str) -> Result<Vec<Talent>> {
     let mut talents = Vec::new();
-    let mut talent_parser = CombatantParser::new(&talent_str);
+    let mut talent_parser = CombatantParser::new(talent_str);
 
-    let mut talent = talent_parser.next();
+    let mut talent = talent_parser.next_group()?;
     while !talent.is_empty() {
-        let talent_ids = talent_parser.parse_array(&talent)?;
-        assert!(talent_ids.len() == 3);
+        let talent_ids = talent_parser.parse_array(talent)?;
+        let [node_id, entry_id, rank] = talent_ids[..] else {
+            return Err(eyre!("expected node, entry and rank for talent - {talent}"));
+        };
         talents.push(Talent {
-            node_id: talent_ids[0],
-            entry_id: talent_ids[1],
-            rank: talent_ids[2],
+            node_id,
+            entry_id,
+            rank,
         });
-        talent = talent_parser.next();
+        talent = talent_parser.next_group()?;
     }
 
     Ok(talents)
@@ -89,24 +110,34 @@ fn parse_talents(talent_str: &str) -> Result<Vec<Talent>> {
 
 fn parse_equipment(equipment_str: &str) -> Result<Vec<Equipment>> {
     let mut parser = CombatantParser::new(equipment_str);
-    let mut equip_str = parser.next();
+    let mut equip_str = parser.next_group()?;
 
     let mut equipment = Vec::new();
     while !equip_str.is_empty() {
         equipment.push(Equipment::new(equip_str)?);
-        equip_str = parser.next();
+        equip_str = parser.next_group()?;
     }
 
     Ok(equipment)
 }
 
-fn parse_tracked_auras(aura_str: &str) -> Result<Vec<TrackedAura>> {
+fn parse_tracked_auras(aura_str: &str, symbols: &SymbolCache) -> Result<Vec<TrackedAura>> {
+    if aura_str.is_empty() {
+        return Ok(Vec::new());
+    }
+
     let auras = aura_str.split(',').collect::<Vec<&str>>();
+    if auras.len() % 3 != 0 {
+        return Err(eyre!(
+            "expected caster, spell id and stacks for each aura - {aura_str}"
+        ));
+    }
+
     auras
-        .chunks(3)
+        .chunks_exact(3)
         .map(|aura| {
             Ok(TrackedAura {
-                caster: Guid(aura[0].to_string()),
+                caster: Guid(symbols.intern(aura[0])),
                 spell_id: aura[1]
                     .parse::<u32>()
                     .wrap_err_with(|| format!("expected spell id for aura - {}", aura[1]))?,
@@ -129,7 +160,7 @@ fn parse_pvp_talents(pvp_str: &str) -> Result<PvpTalents> {
 }
 
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Stats {
     strength: u32,
     agility: u32,
@@ -182,10 +213,211 @@ impl Stats {
             armor: parser.next_numeric::<u32>()?,
         })
     }
+
+    pub fn strength(&self) -> u32 {
+        self.strength
+    }
+
+    pub fn agility(&self) -> u32 {
+        self.agility
+    }
+
+    pub fn stamina(&self) -> u32 {
+        self.stamina
+    }
+
+    pub fn intelligence(&self) -> u32 {
+        self.intelligence
+    }
+
+    pub fn dodge(&self) -> u32 {
+        self.dodge
+    }
+
+    pub fn parry(&self) -> u32 {
+        self.parry
+    }
+
+    pub fn critical_block(&self) -> u32 {
+        self.critical_block
+    }
+
+    pub fn block(&self) -> u32 {
+        self.block
+    }
+
+    pub fn crit_melee(&self) -> u32 {
+        self.crit_melee
+    }
+
+    pub fn crit_ranged(&self) -> u32 {
+        self.crit_ranged
+    }
+
+    pub fn crit_spell(&self) -> u32 {
+        self.crit_spell
+    }
+
+    pub fn speed(&self) -> u32 {
+        self.speed
+    }
+
+    pub fn lifesteal(&self) -> u32 {
+        self.lifesteal
+    }
+
+    pub fn haste_melee(&self) -> u32 {
+        self.haste_melee
+    }
+
+    pub fn haste_ranged(&self) -> u32 {
+        self.haste_ranged
+    }
+
+    pub fn haste_spell(&self) -> u32 {
+        self.haste_spell
+    }
+
+    pub fn avoidance(&self) -> u32 {
+        self.avoidance
+    }
+
+    pub fn mastery(&self) -> u32 {
+        self.mastery
+    }
+
+    pub fn versatility_damage(&self) -> i32 {
+        self.versatility_damage
+    }
+
+    pub fn versatility_healing(&self) -> i32 {
+        self.versatility_healing
+    }
+
+    pub fn versatility_damage_taken(&self) -> i32 {
+        self.versatility_damage_taken
+    }
+
+    pub fn armor(&self) -> u32 {
+        self.armor
+    }
+
+    pub fn primary(&self, stat: PrimaryStat) -> u32 {
+        match stat {
+            PrimaryStat::Strength => self.strength,
+            PrimaryStat::Agility => self.agility,
+            PrimaryStat::Intelligence => self.intelligence,
+        }
+    }
+
+    // The melee, ranged and spell ratings only differ for specs with a talent
+    // that converts one into another so the highest is the one that applies
+    pub fn derived(&self, table: &RatingTable) -> DerivedStats {
+        let crit = self.crit_melee.max(self.crit_ranged).max(self.crit_spell);
+        let haste = self
+            .haste_melee
+            .max(self.haste_ranged)
+            .max(self.haste_spell);
+
+        DerivedStats {
+            crit: table.percent(crit, table.crit),
+            haste: table.percent(haste, table.haste),
+            mastery: table.percent(self.mastery, table.mastery),
+            versatility: table.percent(self.versatility_damage.max(0) as u32, table.versatility),
+        }
+    }
+}
+
+// Secondary stat brackets as (upper bound in percent, share kept) applied to
+// the percentage before diminishing returns
+const DIMINISHING_RETURNS: [(f64, f64); 7] = [
+    (30.0, 1.0),
+    (39.0, 0.9),
+    (47.0, 0.8),
+    (54.0, 0.7),
+    (66.0, 0.6),
+    (126.0, 0.5),
+    (f64::INFINITY, 0.0),
+];
+
+// Rating needed for 1% of each secondary stat at a given level. These change
+// with every expansion (and sometimes mid-season) so older logs need the
+// table they were recorded with.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RatingTable {
+    pub level: u32,
+    // The `(major, minor)` patch the table took effect in
+    pub patch: (u32, u32),
+    pub crit: f64,
+    pub haste: f64,
+    pub mastery: f64,
+    pub versatility: f64,
+}
+
+impl RatingTable {
+    pub const DRAGONFLIGHT: Self = Self::new(70, (10, 0), 180.0, 170.0, 180.0, 205.0);
+    pub const THE_WAR_WITHIN: Self = Self::new(80, (11, 0), 700.0, 660.0, 700.0, 820.0);
+
+    pub const fn new(
+        level: u32,
+        patch: (u32, u32),
+        crit: f64,
+        haste: f64,
+        mastery: f64,
+        versatility: f64,
+    ) -> Self {
+        Self {
+            level,
+            patch,
+            crit,
+            haste,
+            mastery,
+            versatility,
+        }
+    }
+
+    // The latest table for the level as of the log's BUILD_VERSION, e.g.
+    // `11.1.5`
+    pub fn for_build(level: u32, build: &str) -> Option<Self> {
+        let mut parts = build.split('.').map(|part| part.parse::<u32>().ok());
+        let patch = (parts.next()??, parts.next()??);
+
+        [Self::DRAGONFLIGHT, Self::THE_WAR_WITHIN]
+            .into_iter()
+            .filter(|table| table.level == level && table.patch <= patch)
+            .max_by_key(|table| table.patch)
+    }
+
+    fn percent(&self, rating: u32, per_percent: f64) -> f64 {
+        let raw = rating as f64 / per_percent;
+
+        let mut lower = 0.0;
+        let mut percent = 0.0;
+        for (upper, kept) in DIMINISHING_RETURNS {
+            if raw <= lower {
+                break;
+            }
+
+            percent += (raw.min(upper) - lower) * kept;
+            lower = upper;
+        }
+
+        percent
+    }
+}
+
+// Percentages gained from rating alone. Class and spec base values are not
+// included and mastery is in points, before the spec's mastery coefficient.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DerivedStats {
+    pub crit: f64,
+    pub haste: f64,
+    pub mastery: f64,
+    pub versatility: f64,
 }
 
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct PvpStats {
     honor_level: u32,
     season: u32,
@@ -202,17 +434,55 @@ impl PvpStats {
             tier: parser.next_numeric::<u32>()?,
         })
     }
+
+    pub fn honor_level(&self) -> u32 {
+        self.honor_level
+    }
+
+    pub fn season(&self) -> u32 {
+        self.season
+    }
+
+    pub fn rating(&self) -> u32 {
+        self.rating
+    }
+
+    pub fn tier(&self) -> u32 {
+        self.tier
+    }
 }
 
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Talent {
     node_id: u32,
     entry_id: u32,
     rank: u32,
 }
 
-#[derive(Debug, Clone)]
+impl Talent {
+    pub fn new(node_id: u32, entry_id: u32, rank: u32) -> Self {
+        Self {
+            node_id,
+            entry_id,
+            rank,
+        }
+    }
+
+    pub fn node_id(&self) -> u32 {
+        self.node_id
+    }
+
+    pub fn entry_id(&self) -> u32 {
+        self.entry_id
+    }
+
+    pub fn rank(&self) -> u32 {
+        self.rank
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Equipment {
     pub item_id: u32,
     pub item_level: u32,
@@ -226,7 +496,7 @@ impl Equipment {
         let mut parser = CombatantParser::new(gear_str);
         let item_id = parser.next_numeric::<u32>()?;
         let item_level = parser.next_numeric::<u32>()?;
-        let enchantment_str = parser.next();
+        let enchantment_str = parser.next_group()?;
         let enchantment = if enchantment_str.is_empty() {
             None
         } else {
@@ -238,11 +508,16 @@ impl Equipment {
                 })
                 .collect::<Result<Vec<u32>>>()?;
 
-            assert!(values.len() == 3);
+            if values.len() != 3 {
+                return Err(eyre!(
+                    "expected three values for enchantment - {enchantment_str}"
+                ));
+            }
+
             Some((values[0], values[1], values[2]))
         };
         let bonuses = parser
-            .next()
+            .next_group()?
             .split(',')
             .filter_map(|v| {
                 if v.is_empty() {
@@ -256,7 +531,7 @@ impl Equipment {
             })
             .collect::<Result<Vec<u32>>>()?;
 
-        let gem_strs = parser.next();
+        let gem_strs = parser.next_group()?;
         let gems = if gem_strs.is_empty() {
             Vec::new()
         } else {
@@ -268,7 +543,10 @@ impl Equipment {
                 })
                 .collect::<Result<Vec<u32>>>()?;
 
-            assert_eq!(gem_parse.len() % 2, 0);
+            if gem_parse.len() % 2 != 0 {
+                return Err(eyre!("expected gem id and item level pairs - {gem_strs}"));
+            }
+
             gem_parse
                 .chunks(2)
                 .map(|gem| (gem[0], gem[1]))
@@ -283,9 +561,14 @@ impl Equipment {
             gems,
         })
     }
+
+    // Empty slots are still written, with an item id of 0
+    pub fn is_empty(&self) -> bool {
+        self.item_id == 0
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TrackedAura {
     pub caster: Guid,
     pub spell_id: u32,
@@ -319,10 +602,15 @@ impl<'a> CombatantParser<'a> {
             })
             .collect::<Result<Vec<T>>>()
     }
-}
 
-impl<'a> EventParser<'a> for CombatantParser<'a> {
-    fn next(&mut self) -> &'a str {
+    // For fields that are quoted or bracketed, where a truncated line would
+    // otherwise leave the closing character missing
+    pub fn next_group(&mut self) -> Result<&'a str> {
+        let value = self.next_raw();
+        strip_group(value).ok_or_else(|| eyre!("unterminated combatant info field - {value}"))
+    }
+
+    fn next_raw(&mut self) -> &'a str {
         let mut end = self.rest.len();
         let mut new_start = self.rest.len();
         let mut stack = Vec::with_capacity(4);
@@ -339,10 +627,8 @@ impl<'a> EventParser<'a> for CombatantParser<'a> {
                 }
                 '(' => stack.push(')'),
                 '[' => stack.push(']'),
-                ')' | ']' => {
-                    if stack.last() == Some(&ch) {
-                        stack.pop();
-                    }
+                ')' | ']' if stack.last() == Some(&ch) => {
+                    stack.pop();
                 }
                 ch if ch == self.delimiter && stack.is_empty() => {
                     end = i;
@@ -355,12 +641,30 @@ impl<'a> EventParser<'a> for CombatantParser<'a> {
 
         let value = &self.rest[..end];
         self.rest = &self.rest[new_start..];
+        value
+    }
+}
 
-        if value.starts_with('"') || value.starts_with('(') || value.starts_with('[') {
-            &value[1..value.len() - 1]
-        } else {
-            value
-        }
+impl<'a> EventParser<'a> for CombatantParser<'a> {
+    fn next(&mut self) -> &'a str {
+        let value = self.next_raw();
+        strip_group(value).unwrap_or(value)
+    }
+}
+
+// `None` when the value opens a group it never closes
+fn strip_group(value: &str) -> Option<&str> {
+    let close = match value.chars().next() {
+        Some('"') => '"',
+        Some('(') => ')',
+        Some('[') => ']',
+        _ => return Some(value),
+    };
+
+    if value.len() >= 2 && value.ends_with(close) {
+        Some(&value[1..value.len() - 1])
+    } else {
+        None
     }
 }
 
@@ -385,8 +689,66 @@ mod combatant_tests {
 
         let mut parser = CombatantParser::new(test);
         let result = parser.next();
-        let mut parser = CombatantParser::new(&result);
+        let mut parser = CombatantParser::new(result);
         let result = parser.next();
         eprintln!("{result:?}");
     }
+
+    #[test]
+    fn ratings_become_percentages() -> Result<()> {
+        // Strength to armor, with 14000 crit, 26400 haste, 7000 mastery and
+        // 4100 versatility rating
+        let input = "1000,50000,400000,2000,0,0,14000,0,14000,14000,14000,0,0,26400,26400,26400,0,7000,4100,4100,2050,60000";
+        let stats = Stats::new(&mut CombatantParser::new(input))?;
+        assert_eq!(stats.agility(), 50000);
+        assert_eq!(stats.versatility_damage_taken(), 2050);
+        assert_eq!(stats.primary(PrimaryStat::Agility), 50000);
+
+        let table = RatingTable::for_build(80, "11.1.5").expect("known level");
+        let derived = stats.derived(&table);
+        assert!((derived.crit - 20.0).abs() < 1e-9);
+        // 40% before diminishing returns, so 30 + 9 * 0.9 + 1 * 0.8
+        assert!((derived.haste - 38.9).abs() < 1e-9);
+        assert!((derived.mastery - 10.0).abs() < 1e-9);
+        assert!((derived.versatility - 5.0).abs() < 1e-9);
+
+        assert_eq!(
+            Specialization::OutlawRogue.primary_stat(),
+            Some(PrimaryStat::Agility)
+        );
+        assert_eq!(Specialization::Unknown(9999).primary_stat(), None);
+        assert_eq!(RatingTable::for_build(75, "11.1.5"), None);
+        assert_eq!(RatingTable::for_build(80, "10.2.7"), None);
+        assert_eq!(RatingTable::for_build(80, "11"), None);
+        assert_eq!(
+            RatingTable::for_build(70, "10.2.7"),
+            Some(RatingTable::DRAGONFLIGHT)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_combatant_info_is_an_error() {
+        let symbols = SymbolCache::new(std::sync::Arc::new(crate::intern::Interner::new()));
+
+        assert!(
+            parse_tracked_auras("", &symbols)
+                .expect("no auras")
+                .is_empty()
+        );
+        assert!(parse_tracked_auras("Player-1-0001,1459", &symbols).is_err());
+        assert!(parse_talents("(1,2,1),(3,4)").is_err());
+        assert!(Equipment::new("100,600,(1,2),(),()").is_err());
+        assert!(Equipment::new("100,600,(),(),(213746)").is_err());
+        assert!(Equipment::new("100,600,(7340,0,0),(4802),(213746,600)").is_ok());
+        assert!(Equipment::new("100,600,(").is_err());
+        assert!(parse_talents("(1,2,1),(").is_err());
+
+        // Cut off part way through the gear
+        let line = "Player-1-0001,1,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,62,[(1,10,1)],(0,0,0,0),[";
+        assert!(Combatant::new(line, &symbols).is_err());
+        let mut parser = CombatantParser::new("[");
+        assert_eq!(parser.next(), "[");
+    }
 }
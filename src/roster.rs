@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use jiff::civil::DateTime;
+
+use crate::{
+    event::{Event, Guid},
+    intern::{Symbol, SymbolTable},
+    parser::ParsedEvent,
+    segment::{SegmentKind, segments},
+    types::{Class, Faction, Role, Specialization},
+};
+
+const REGIONS: [&str; 5] = ["US", "EU", "KR", "TW", "CN"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub guid: Guid,
+    // `None` when the player never did anything that put their name in the
+    // log, e.g. they were dead or afk for the whole pull
+    pub name: Option<String>,
+    pub realm: Option<String>,
+    // Only written on retail, e.g. `EU`
+    pub region: Option<String>,
+    pub faction: Faction,
+    pub spec: Specialization,
+    pub class: Option<Class>,
+    pub role: Option<Role>,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Composition {
+    pub tanks: usize,
+    pub healers: usize,
+    pub damage: usize,
+    pub unknown: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Roster {
+    pub encounter_id: u32,
+    pub encounter_name: String,
+    pub start: DateTime,
+    // In the order the client wrote their COMBATANT_INFO
+    pub members: Vec<Member>,
+}
+
+impl Roster {
+    pub fn get(&self, guid: Guid) -> Option<&Member> {
+        self.members.iter().find(|m| m.guid == guid)
+    }
+
+    pub fn contains(&self, guid: Guid) -> bool {
+        self.get(guid).is_some()
+    }
+
+    pub fn composition(&self) -> Composition {
+        let mut composition = Composition::default();
+        for member in &self.members {
+            match member.role {
+                Some(Role::Tank) => composition.tanks += 1,
+                Some(Role::Healer) => composition.healers += 1,
+                Some(Role::Damage) => composition.damage += 1,
+                None => composition.unknown += 1,
+            }
+        }
+
+        composition
+    }
+
+    pub fn classes(&self) -> Vec<(Class, usize)> {
+        let mut classes: Vec<(Class, usize)> = Vec::new();
+        for class in self.members.iter().filter_map(|m| m.class) {
+            match classes.iter_mut().find(|(c, _)| *c == class) {
+                Some((_, count)) => *count += 1,
+                None => classes.push((class, 1)),
+            }
+        }
+
+        classes.sort();
+        classes
+    }
+}
+
+// Every pull of a log along with everyone who took part in any of them
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaidNight {
+    pub rosters: Vec<Roster>,
+    // In the order they were first seen
+    pub players: Vec<Guid>,
+}
+
+impl RaidNight {
+    pub fn new(events: &[ParsedEvent<'_>], symbols: &SymbolTable) -> Self {
+        let rosters = rosters(events, symbols);
+        let mut players: Vec<Guid> = Vec::new();
+        for member in rosters.iter().flat_map(|r| &r.members) {
+            if !players.contains(&member.guid) {
+                players.push(member.guid);
+            }
+        }
+
+        Self { rosters, players }
+    }
+
+    // Players from the night who sat out a pull
+    pub fn benched(&self, roster: &Roster) -> Vec<Guid> {
+        self.players
+            .iter()
+            .copied()
+            .filter(|guid| !roster.contains(*guid))
+            .collect()
+    }
+
+    pub fn attendance(&self, guid: Guid) -> usize {
+        self.rosters.iter().filter(|r| r.contains(guid)).count()
+    }
+}
+
+pub fn rosters(events: &[ParsedEvent<'_>], symbols: &SymbolTable) -> Vec<Roster> {
+    let names = names(events);
+
+    segments(events)
+        .into_iter()
+        .filter_map(|segment| {
+            let SegmentKind::Encounter {
+                encounter_id, name, ..
+            } = &segment.kind
+            else {
+                return None;
+            };
+
+            let members = segment
+                .combatants(events)
+                .into_iter()
+                .map(|combatant| {
+                    let (name, realm, region) = match names.get(&combatant.guid) {
+                        Some(name) => split_name(symbols.resolve(*name)),
+                        None => (None, None, None),
+                    };
+
+                    Member {
+                        guid: combatant.guid,
+                        name,
+                        realm,
+                        region,
+                        faction: combatant.faction,
+                        spec: combatant.spec,
+                        class: combatant.spec.class(),
+                        role: combatant.spec.role(),
+                    }
+                })
+                .collect();
+
+            Some(Roster {
+                encounter_id: *encounter_id,
+                encounter_name: name.clone(),
+                start: segment.start,
+                members,
+            })
+        })
+        .collect()
+}
+
+// Names only appear on combat events, so take them from any group member
+// doing anything at all
+fn names(events: &[ParsedEvent<'_>]) -> HashMap<Guid, Symbol> {
+    let mut names = HashMap::new();
+    for event in events {
+        let Event::Combat(combat) = &event.event else {
+            continue;
+        };
+
+        for target in [combat.src, combat.dst].into_iter().flatten() {
+            if target.unit_flags.is_player() && target.unit_flags.is_grouped() {
+                names.entry(target.guid).or_insert(target.name);
+            }
+        }
+    }
+
+    names
+}
+
+// Players are written as `Name-Realm`, with the region appended on retail
+fn split_name(full: &str) -> (Option<String>, Option<String>, Option<String>) {
+    let Some((name, rest)) = full.split_once('-') else {
+        return (Some(full.to_string()), None, None);
+    };
+
+    let (realm, region) = match rest.rsplit_once('-') {
+        Some((realm, region)) if REGIONS.contains(&region) => (realm, Some(region.to_string())),
+        _ => (rest, None),
+    };
+
+    (Some(name.to_string()), Some(realm.to_string()), region)
+}
+
+#[cfg(test)]
+mod roster_tests {
+    use super::*;
+    use crate::testing::{CombatantLine, parse_with_symbols, pull};
+
+    fn group(start: &str, end: &str, players: &[(&str, u16)]) -> String {
+        let combatants = players
+            .iter()
+            .map(|(guid, spec)| CombatantLine::new(guid, *spec).with_timestamp(start).line())
+            .collect::<Vec<_>>();
+
+        pull(start, end, &combatants)
+    }
+
+    #[test]
+    fn rosters_are_built_per_pull() {
+        let log = [
+            group(
+                "20:00:00.000",
+                "20:01:00.000",
+                &[("Player-1-0001", 73), ("Player-1-0002", 65)],
+            ),
+            "4/19/2026 20:02:00.000  SPELL_CAST_SUCCESS,Player-1-0001,\"Tank-Realm-EU\",0x514,0x0,0000000000000000,nil,0x80000000,0x80000000,23922,\"Shield Slam\",0x1,Player-1-0001,0000000000000000,100,200,300,400,500,0,0,0,1,100,100,0,-10.5,20.25,2769,1.5,639\n".to_string(),
+            group(
+                "20:05:00.000",
+                "20:06:00.000",
+                &[
+                    ("Player-1-0001", 73),
+                    ("Player-1-0003", 62),
+                    ("Player-1-0004", 1473),
+                ],
+            ),
+        ]
+        .concat();
+
+        let (events, symbols) = parse_with_symbols(&log);
+
+        let night = RaidNight::new(&events, &symbols);
+        assert_eq!(night.rosters.len(), 2);
+        assert_eq!(night.players.len(), 4);
+
+        let first = &night.rosters[0];
+        let tank = first.get(night.players[0]).expect("tank in first pull");
+        assert_eq!(tank.name.as_deref(), Some("Tank"));
+        assert_eq!(tank.realm.as_deref(), Some("Realm"));
+        assert_eq!(tank.region.as_deref(), Some("EU"));
+        assert_eq!(tank.class, Some(Class::Warrior));
+        assert_eq!(tank.role, Some(Role::Tank));
+        assert_eq!(
+            first.composition(),
+            Composition {
+                tanks: 1,
+                healers: 1,
+                damage: 0,
+                unknown: 0,
+            }
+        );
+
+        let second = &night.rosters[1];
+        assert_eq!(second.composition().damage, 2);
+        assert_eq!(
+            second.classes(),
+            vec![(Class::Evoker, 1), (Class::Mage, 1), (Class::Warrior, 1)]
+        );
+        assert_eq!(night.benched(second), vec![night.players[1]]);
+        assert_eq!(night.attendance(night.players[0]), 2);
+        assert_eq!(night.attendance(night.players[3]), 1);
+    }
+
+    #[test]
+    fn names_are_split_into_realm_and_region() {
+        let split = |full| {
+            let (name, realm, region) = split_name(full);
+            (name.unwrap_or_default(), realm, region)
+        };
+
+        assert_eq!(
+            split("Tank-Twisting Nether-EU"),
+            (
+                "Tank".to_string(),
+                Some("Twisting Nether".to_string()),
+                Some("EU".to_string())
+            )
+        );
+        assert_eq!(
+            split("Tank-Faerlina"),
+            ("Tank".to_string(), Some("Faerlina".to_string()), None)
+        );
+        assert_eq!(split("Tank"), ("Tank".to_string(), None, None));
+    }
+}
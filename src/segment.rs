@@ -0,0 +1,232 @@
+use std::ops::Range;
+
+use jiff::{SignedDuration, civil::DateTime};
+
+use crate::{
+    event::{Difficulty, Event},
+    parser::ParsedEvent,
+    player::Combatant,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SegmentKind {
+    Encounter {
+        encounter_id: u32,
+        name: String,
+        difficulty: Difficulty,
+        group_size: u32,
+    },
+    ChallengeMode {
+        instance_id: u32,
+        zone_name: String,
+        challenge_mode_id: u32,
+        keystone_level: u32,
+    },
+    Arena {
+        instance_id: u32,
+        match_type: String,
+        // The team the recording player was on
+        team_id: u32,
+    },
+}
+
+// A pull or a keystone run. Boss pulls inside a key show up as their own
+// segments alongside the key itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub kind: SegmentKind,
+    pub start: DateTime,
+    pub end: DateTime,
+    // `None` when the log ends (or the client disconnects) before the end
+    // event is written
+    pub success: Option<bool>,
+    pub events: Range<usize>,
+}
+
+impl Segment {
+    pub fn duration(&self) -> SignedDuration {
+        self.end.duration_since(self.start)
+    }
+
+    pub fn slice<'e, 'a>(&self, events: &'e [ParsedEvent<'a>]) -> &'e [ParsedEvent<'a>] {
+        &events[self.events.clone()]
+    }
+
+    // The COMBATANT_INFO written for everyone in the group as the segment
+    // starts, in the order the client wrote them. When a player is written
+    // twice the last one wins.
+    pub fn combatants<'e>(&self, events: &'e [ParsedEvent<'_>]) -> Vec<&'e Combatant> {
+        let mut combatants: Vec<&Combatant> = Vec::new();
+        for event in self.slice(events) {
+            let Event::Combatant(combatant) = &event.event else {
+                continue;
+            };
+
+            match combatants.iter_mut().find(|c| c.guid == combatant.guid) {
+                Some(existing) => *existing = combatant,
+                None => combatants.push(combatant),
+            }
+        }
+
+        combatants
+    }
+}
+
+struct Open {
+    kind: SegmentKind,
+    start: usize,
+}
+
+pub fn segments(events: &[ParsedEvent<'_>]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut encounter: Option<Open> = None;
+    let mut key: Option<Open> = None;
+    let mut arena: Option<Open> = None;
+
+    for (i, event) in events.iter().enumerate() {
+        match &event.event {
+            Event::EncounterStart(start) => {
+                if let Some(open) = encounter.take() {
+                    segments.push(close(events, open, i, None));
+                }
+
+                encounter = Some(Open {
+                    kind: SegmentKind::Encounter {
+                        encounter_id: start.encounter_id,
+                        name: start.encounter_name.to_string(),
+                        difficulty: start.difficulty,
+                        group_size: start.group_size,
+                    },
+                    start: i,
+                });
+            }
+            Event::EncounterEnd(end) => {
+                if let Some(open) = encounter.take() {
+                    segments.push(close(events, open, i + 1, Some(end.success)));
+                }
+            }
+            Event::ChallengeModeStart(start) => {
+                if let Some(open) = key.take() {
+                    segments.push(close(events, open, i, None));
+                }
+
+                key = Some(Open {
+                    kind: SegmentKind::ChallengeMode {
+                        instance_id: start.instance_id,
+                        zone_name: start.zone_name.to_string(),
+                        challenge_mode_id: start.challenge_mode_id,
+                        keystone_level: start.keystone_level,
+                    },
+                    start: i,
+                });
+            }
+            Event::ChallengeModeEnd(end) => {
+                if let Some(open) = key.take() {
+                    segments.push(close(events, open, i + 1, Some(end.success)));
+                }
+            }
+            Event::ArenaStart(start) => {
+                if let Some(open) = arena.take() {
+                    segments.push(close(events, open, i, None));
+                }
+
+                arena = Some(Open {
+                    kind: SegmentKind::Arena {
+                        instance_id: start.instance_id,
+                        match_type: start.match_type.to_string(),
+                        team_id: start.team_id,
+                    },
+                    start: i,
+                });
+            }
+            Event::ArenaEnd(end) => {
+                if let Some(open) = arena.take() {
+                    let SegmentKind::Arena { team_id, .. } = open.kind else {
+                        unreachable!("only arena segments are opened here");
+                    };
+
+                    let success = Some(end.winning_team == team_id);
+                    segments.push(close(events, open, i + 1, success));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for open in [encounter, key, arena].into_iter().flatten() {
+        segments.push(close(events, open, events.len(), None));
+    }
+
+    segments.sort_by_key(|segment| segment.events.start);
+    segments
+}
+
+fn close(events: &[ParsedEvent<'_>], open: Open, end: usize, success: Option<bool>) -> Segment {
+    Segment {
+        kind: open.kind,
+        start: events[open.start].timestamp,
+        end: events[end - 1].timestamp,
+        success,
+        events: open.start..end,
+    }
+}
+
+#[cfg(test)]
+mod segment_tests {
+    use super::*;
+    use crate::{
+        testing::{CombatantLine, parse, pull},
+        types::Specialization,
+    };
+
+    const LOG: &str = "4/19/2026 20:00:00.000  CHALLENGE_MODE_START,\"Ara-Kara, City of Echoes\",2660,503,12,[10,147,9,152]\n\
+        4/19/2026 20:05:00.000  ENCOUNTER_START,2901,\"Avanoxx\",8,5,2660\n\
+        4/19/2026 20:07:30.000  ENCOUNTER_END,2901,\"Avanoxx\",8,5,1,150000\n\
+        4/19/2026 20:10:00.000  ENCOUNTER_START,2906,\"Anub'zekt\",8,5,2660\n\
+        4/19/2026 20:30:00.000  CHALLENGE_MODE_END,2660,1,12,1800000,3012.5,3040.1\n";
+
+    #[test]
+    fn it_splits_keys_and_pulls() {
+        let events = parse(LOG);
+        let segments = segments(&events);
+
+        assert_eq!(segments.len(), 3);
+        assert!(matches!(
+            &segments[0].kind,
+            SegmentKind::ChallengeMode { zone_name, keystone_level: 12, .. }
+                if zone_name == "Ara-Kara, City of Echoes"
+        ));
+        assert_eq!(segments[0].events, 0..5);
+        assert_eq!(segments[0].success, Some(true));
+        assert_eq!(segments[0].duration(), SignedDuration::from_mins(30));
+
+        assert_eq!(segments[1].events, 1..3);
+        assert_eq!(segments[1].success, Some(true));
+
+        // Never saw an ENCOUNTER_END so runs to the end of the log
+        assert_eq!(segments[2].events, 3..5);
+        assert_eq!(segments[2].success, None);
+    }
+
+    #[test]
+    fn combatants_written_twice_keep_the_latest() {
+        let combatant = |guid, spec| CombatantLine::new(guid, spec).line();
+        let log = pull(
+            "20:00:00.000",
+            "20:05:00.000",
+            &[
+                combatant("Player-1-0001", 62),
+                combatant("Player-1-0002", 71),
+                combatant("Player-1-0001", 63),
+            ],
+        );
+
+        let events = parse(&log);
+        let segments = segments(&events);
+        let combatants = segments[0].combatants(&events);
+
+        assert_eq!(combatants.len(), 2);
+        assert_eq!(combatants[0].spec, Specialization::FireMage);
+        assert_eq!(combatants[1].spec, Specialization::ArmsWarrior);
+    }
+}
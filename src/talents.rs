@@ -0,0 +1,401 @@
+use eyre::{Result, eyre};
+
+use crate::{
+    player::{Combatant, Talent},
+    types::Specialization,
+};
+
+// The version the client currently writes, version 1 strings (from before
+// granted nodes were split out) can still be read
+const LOADOUT_VERSION: u32 = 2;
+
+const VERSION_BITS: u32 = 8;
+const SPEC_BITS: u32 = 16;
+const HASH_BITS: u32 = 8;
+const RANK_BITS: u32 = 6;
+const CHOICE_BITS: u32 = 2;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TalentNode {
+    pub node_id: u32,
+    // More than one entry makes this a choice node
+    pub entries: Vec<u32>,
+    pub max_rank: u32,
+    // Given for free with the spec rather than bought with points
+    pub granted: bool,
+}
+
+// The log only records the talents that were taken so the rest of the tree,
+// in the order the client serialises it, has to come from game data
+#[derive(Debug, Clone, PartialEq)]
+pub struct TalentTree {
+    pub spec: Specialization,
+    // All zeroes skips the client's check that the tree hasn't changed
+    pub hash: [u8; 16],
+    pub nodes: Vec<TalentNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loadout {
+    pub version: u32,
+    pub spec: Specialization,
+    pub hash: [u8; 16],
+    pub talents: Vec<Talent>,
+}
+
+impl TalentTree {
+    pub fn new(spec: Specialization, nodes: Vec<TalentNode>) -> Self {
+        Self {
+            spec,
+            hash: [0; 16],
+            nodes,
+        }
+    }
+
+    pub fn with_hash(mut self, hash: [u8; 16]) -> Self {
+        self.hash = hash;
+        self
+    }
+
+    pub fn export(&self, talents: &[Talent]) -> Result<String> {
+        if let Some(talent) = talents
+            .iter()
+            .find(|talent| !self.nodes.iter().any(|n| n.node_id == talent.node_id()))
+        {
+            return Err(eyre!(
+                "talent node {} is not part of the {} tree",
+                talent.node_id(),
+                self.spec
+            ));
+        }
+
+        let mut writer = BitWriter::default();
+        writer.write(LOADOUT_VERSION, VERSION_BITS);
+        writer.write(self.spec.id() as u32, SPEC_BITS);
+        for byte in self.hash {
+            writer.write(byte as u32, HASH_BITS);
+        }
+
+        for node in &self.nodes {
+            let Some(talent) = talents
+                .iter()
+                .find(|talent| talent.node_id() == node.node_id && talent.rank() > 0)
+            else {
+                writer.write(0, 1);
+                continue;
+            };
+
+            // Granted nodes stop after the purchased bit
+            writer.write(1, 1);
+            writer.write(!node.granted as u32, 1);
+            if node.granted {
+                continue;
+            }
+
+            let partial = talent.rank() < node.max_rank;
+            writer.write(partial as u32, 1);
+            if partial {
+                writer.write(talent.rank(), RANK_BITS);
+            }
+
+            let choice = node.entries.len() > 1;
+            writer.write(choice as u32, 1);
+            if choice {
+                let index = node
+                    .entries
+                    .iter()
+                    .position(|entry| *entry == talent.entry_id())
+                    .ok_or_else(|| {
+                        eyre!(
+                            "entry {} is not a choice on node {}",
+                            talent.entry_id(),
+                            node.node_id
+                        )
+                    })?;
+                writer.write(index as u32, CHOICE_BITS);
+            }
+        }
+
+        Ok(writer.finish())
+    }
+
+    pub fn import(&self, loadout: &str) -> Result<Loadout> {
+        let mut reader = BitReader::new(loadout)?;
+        let version = reader.read(VERSION_BITS)?;
+        if version == 0 || version > LOADOUT_VERSION {
+            return Err(eyre!("unsupported loadout version - {version}"));
+        }
+
+        let spec = Specialization::from(reader.read(SPEC_BITS)? as u16);
+        if spec != self.spec {
+            return Err(eyre!(
+                "loadout is for {spec} but the tree is for {}",
+                self.spec
+            ));
+        }
+
+        let mut hash = [0; 16];
+        for byte in hash.iter_mut() {
+            *byte = reader.read(HASH_BITS)? as u8;
+        }
+
+        if hash != [0; 16] && self.hash != [0; 16] && hash != self.hash {
+            return Err(eyre!("loadout was exported from a different {spec} tree"));
+        }
+
+        let mut talents = Vec::new();
+        for node in &self.nodes {
+            if reader.read(1)? == 0 {
+                continue;
+            }
+
+            // Version 1 has no purchased bit, every selected node is written
+            // in full
+            let purchased = version < 2 || reader.read(1)? == 1;
+            let first = node
+                .entries
+                .first()
+                .copied()
+                .ok_or_else(|| eyre!("node {} has no entries", node.node_id))?;
+            if !purchased {
+                talents.push(Talent::new(node.node_id, first, node.max_rank));
+                continue;
+            }
+
+            let rank = if reader.read(1)? == 1 {
+                reader.read(RANK_BITS)?
+            } else {
+                node.max_rank
+            };
+
+            let entry = if reader.read(1)? == 1 {
+                let index = reader.read(CHOICE_BITS)? as usize;
+                node.entries.get(index).copied().ok_or_else(|| {
+                    eyre!("choice {index} is out of range on node {}", node.node_id)
+                })?
+            } else {
+                first
+            };
+
+            talents.push(Talent::new(node.node_id, entry, rank));
+        }
+
+        Ok(Loadout {
+            version,
+            spec,
+            hash,
+            talents,
+        })
+    }
+}
+
+impl Combatant {
+    pub fn loadout(&self, tree: &TalentTree) -> Result<String> {
+        if tree.spec != self.spec {
+            return Err(eyre!(
+                "combatant is {} but the tree is for {}",
+                self.spec,
+                tree.spec
+            ));
+        }
+
+        tree.export(&self.talents)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TalentDiff {
+    pub added: Vec<Talent>,
+    pub removed: Vec<Talent>,
+    // Same node with a different choice or rank, as (from, to)
+    pub changed: Vec<(Talent, Talent)>,
+}
+
+impl TalentDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+// What has to change to go from one build to another, e.g. a player's
+// talents against a reference loadout
+pub fn diff(from: &[Talent], to: &[Talent]) -> TalentDiff {
+    let mut diff = TalentDiff::default();
+
+    for talent in to {
+        match from.iter().find(|t| t.node_id() == talent.node_id()) {
+            Some(old) if old != talent => diff.changed.push((*old, *talent)),
+            Some(_) => {}
+            None => diff.added.push(*talent),
+        }
+    }
+
+    diff.removed = from
+        .iter()
+        .filter(|talent| !to.iter().any(|t| t.node_id() == talent.node_id()))
+        .copied()
+        .collect();
+
+    diff
+}
+
+// The client packs values least significant bit first into six bit base64
+// characters with no padding
+#[derive(Default)]
+struct BitWriter {
+    output: String,
+    current: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, width: u32) {
+        for i in 0..width {
+            self.current |= ((value >> i) & 1) << self.bits;
+            self.bits += 1;
+            if self.bits == 6 {
+                self.flush();
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        self.output.push(ALPHABET[self.current as usize] as char);
+        self.current = 0;
+        self.bits = 0;
+    }
+
+    fn finish(mut self) -> String {
+        if self.bits > 0 {
+            self.flush();
+        }
+
+        self.output
+    }
+}
+
+struct BitReader {
+    values: Vec<u8>,
+    position: usize,
+}
+
+impl BitReader {
+    fn new(input: &str) -> Result<Self> {
+        let values = input
+            .trim()
+            .bytes()
+            .map(|ch| {
+                ALPHABET
+                    .iter()
+                    .position(|a| *a == ch)
+                    .map(|value| value as u8)
+                    .ok_or_else(|| eyre!("invalid loadout character - {}", ch as char))
+            })
+            .collect::<Result<Vec<u8>>>()?;
+
+        Ok(Self {
+            values,
+            position: 0,
+        })
+    }
+
+    fn read(&mut self, width: u32) -> Result<u32> {
+        let mut value = 0;
+        for i in 0..width {
+            let bit = self
+                .values
+                .get(self.position / 6)
+                .map(|v| (v >> (self.position % 6)) & 1)
+                .ok_or_else(|| eyre!("loadout string ended early"))?;
+            value |= (bit as u32) << i;
+            self.position += 1;
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod talents_tests {
+    use super::*;
+
+    fn tree() -> TalentTree {
+        let node = |node_id, entries: &[u32], max_rank, granted| TalentNode {
+            node_id,
+            entries: entries.to_vec(),
+            max_rank,
+            granted,
+        };
+
+        TalentTree::new(
+            Specialization::BeastMasteryHunter,
+            vec![
+                node(100, &[1000], 1, true),
+                node(101, &[1010], 2, false),
+                node(102, &[1020, 1021], 1, false),
+                node(103, &[1030], 1, false),
+                node(104, &[1040], 3, false),
+            ],
+        )
+    }
+
+    // Laid out by hand from the client's export format: version, spec and
+    // hash, then per node selected, purchased, partial rank and choice bits
+    const EXPORTED: &str = "C0PAAAAAAAAAAAAAAAAAAAAAA0s5CA";
+    const EXPORTED_V1: &str = "B0PAAAAAAAAAAAAAAAAAAAAAAk0sA";
+
+    #[test]
+    fn loadouts_use_the_client_format() -> Result<()> {
+        let tree = tree();
+        let talents = vec![
+            Talent::new(100, 1000, 1),
+            Talent::new(101, 1010, 2),
+            Talent::new(102, 1021, 1),
+            Talent::new(104, 1040, 2),
+        ];
+
+        let loadout = tree.import(EXPORTED)?;
+        assert_eq!(loadout.version, 2);
+        assert_eq!(loadout.spec, Specialization::BeastMasteryHunter);
+        assert_eq!(loadout.hash, [0; 16]);
+        assert_eq!(loadout.talents, talents);
+        assert_eq!(tree.export(&talents)?, EXPORTED);
+
+        let loadout = tree.import(EXPORTED_V1)?;
+        assert_eq!(loadout.version, 1);
+        assert_eq!(loadout.talents, talents);
+
+        assert!(tree.export(&[Talent::new(999, 1, 1)]).is_err());
+        assert!(tree.export(&[Talent::new(102, 1, 1)]).is_err());
+        let mut other = tree.clone();
+        other.spec = Specialization::SurvivalHunter;
+        assert!(other.import(EXPORTED).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn builds_can_be_diffed() {
+        let from = [
+            Talent::new(101, 1010, 2),
+            Talent::new(102, 1020, 1),
+            Talent::new(103, 1030, 1),
+        ];
+        let to = [
+            Talent::new(101, 1010, 2),
+            Talent::new(102, 1021, 1),
+            Talent::new(104, 1040, 3),
+        ];
+
+        let diff = diff(&from, &to);
+        assert_eq!(diff.added, vec![Talent::new(104, 1040, 3)]);
+        assert_eq!(diff.removed, vec![Talent::new(103, 1030, 1)]);
+        assert_eq!(
+            diff.changed,
+            vec![(Talent::new(102, 1020, 1), Talent::new(102, 1021, 1))]
+        );
+        assert!(super::diff(&to, &to).is_empty());
+    }
+}
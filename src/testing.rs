@@ -0,0 +1,125 @@
+// Scaffolding shared by the tests of everything built on parsed events
+use jiff::civil::Time;
+
+use crate::{
+    intern::SymbolTable,
+    parser::{BorrowedEventParser, ParsedEvent},
+};
+
+pub fn parse(log: &str) -> Vec<ParsedEvent<'_>> {
+    BorrowedEventParser::new(log)
+        .collect::<eyre::Result<Vec<ParsedEvent>>>()
+        .expect("valid test log")
+}
+
+// For tests that need to resolve interned names
+pub fn parse_with_symbols(log: &str) -> (Vec<ParsedEvent<'_>>, SymbolTable) {
+    let mut parser = BorrowedEventParser::new(log);
+    let events = parser
+        .by_ref()
+        .collect::<eyre::Result<Vec<ParsedEvent>>>()
+        .expect("valid test log");
+
+    (events, parser.symbols())
+}
+
+// A heroic Vexie kill around `lines`, with times as `HH:MM:SS.fff`
+pub fn pull(start: &str, end: &str, lines: &[String]) -> String {
+    let time = |time: &str| time.parse::<Time>().expect("valid pull time");
+    let fight_time = time(end).duration_since(time(start));
+
+    format!(
+        "4/19/2026 {start}  ENCOUNTER_START,3009,\"Vexie\",16,20,2769\n{}4/19/2026 {end}  ENCOUNTER_END,3009,\"Vexie\",16,20,1,{}\n",
+        lines.concat(),
+        fight_time.as_millis()
+    )
+}
+
+// Builds COMBATANT_INFO lines, by default with nothing equipped and only
+// the player's own Arcane Intellect up
+pub struct CombatantLine {
+    timestamp: String,
+    guid: String,
+    faction: u32,
+    strength: u32,
+    spec: u16,
+    talents: String,
+    gear: Vec<String>,
+    auras: String,
+    pvp_talents: String,
+    pvp_stats: String,
+}
+
+impl CombatantLine {
+    pub fn new(guid: &str, spec: u16) -> Self {
+        Self {
+            timestamp: "20:00:00.100".to_string(),
+            guid: guid.to_string(),
+            faction: 1,
+            strength: 1,
+            spec,
+            talents: "(1,10,1)".to_string(),
+            gear: vec!["(0,0,(),(),())".to_string(); 18],
+            auras: format!("{guid},1459,1"),
+            pvp_talents: "0,0,0,0".to_string(),
+            pvp_stats: "0,0,0,0".to_string(),
+        }
+    }
+
+    pub fn with_timestamp(mut self, timestamp: &str) -> Self {
+        self.timestamp = timestamp.to_string();
+        self
+    }
+
+    // The team in arenas
+    pub fn with_faction(mut self, faction: u32) -> Self {
+        self.faction = faction;
+        self
+    }
+
+    pub fn with_strength(mut self, strength: u32) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    // `(node,entry,rank)` tuples
+    pub fn with_talents(mut self, talents: &str) -> Self {
+        self.talents = talents.to_string();
+        self
+    }
+
+    // One item per slot, e.g. `(id,level,(enchant),(bonuses),(gems))`
+    pub fn with_gear(mut self, gear: impl IntoIterator<Item = String>) -> Self {
+        self.gear = gear.into_iter().collect();
+        self
+    }
+
+    // Flat `caster,spell,stacks` triples
+    pub fn with_auras(mut self, auras: &str) -> Self {
+        self.auras = auras.to_string();
+        self
+    }
+
+    // The four talent ids and the `honor,season,rating,tier` stats
+    pub fn with_pvp(mut self, talents: &str, stats: &str) -> Self {
+        self.pvp_talents = talents.to_string();
+        self.pvp_stats = stats.to_string();
+        self
+    }
+
+    pub fn line(&self) -> String {
+        format!(
+            "4/19/2026 {}  COMBATANT_INFO,{},{},{},2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,{},[{}],({}),[{}],[{}],{}\n",
+            self.timestamp,
+            self.guid,
+            self.faction,
+            self.strength,
+            self.spec,
+            self.talents,
+            self.pvp_talents,
+            self.gear.join(","),
+            self.auras,
+            self.pvp_stats
+        )
+    }
+}
@@ -1,29 +1,29 @@
-use eyre::{Report, Result, eyre};
+use std::borrow::Cow;
 
-#[derive(Debug, Clone)]
-pub struct Guid(pub String);
+use crate::intern::Symbol;
+use eyre::{Report, Result, eyre};
 
-impl std::fmt::Display for Guid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Guid(pub Symbol);
 
-impl std::ops::Deref for Guid {
-    type Target = String;
-    fn deref(&self) -> &Self::Target {
-        &self.0
+impl From<Guid> for Symbol {
+    fn from(value: Guid) -> Self {
+        value.0
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Target {
     pub guid: Guid,
-    pub name: String,
+    pub name: Symbol,
     pub unit_flags: UnitFlags,
     pub raid_flags: RaidFlag,
 }
 
+pub(crate) fn owned(value: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(value.into_owned())
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum EventType {
     SpellAuraAppliedDose,
@@ -96,79 +96,101 @@ pub enum EventType {
     ChallengeModeEnd,
     WorldMarkerPlaced,
     WorldMarkerRemoved,
+    // Anything the client writes that we don't know about yet
+    Unknown(Symbol),
 }
 
 impl EventType {
+    pub fn is_support(&self) -> bool {
+        matches!(
+            self,
+            Self::SpellDamageSupport
+                | Self::RangeDamageSupport
+                | Self::SwingDamageLandedSupport
+                | Self::SpellPeriodicDamageSupport
+                | Self::SpellHealSupport
+                | Self::SpellPeriodicHealSupport
+                | Self::SpellAbsorbedSupport
+        )
+    }
+
+    // Written alongside another event for the same hit, so totals skip it
+    pub fn is_duplicate(&self) -> bool {
+        matches!(self, Self::SwingDamageLanded)
+    }
+
     pub fn has_spell_parameters(&self) -> bool {
-        match self {
+        !matches!(
+            self,
             Self::ArenaMatchStart
-            | Self::ArenaMatchEnd
-            | Self::EncounterStart
-            | Self::EncounterEnd
-            | Self::ChallengeModeStart
-            | Self::ChallengeModeEnd
-            | Self::WorldMarkerPlaced
-            | Self::WorldMarkerRemoved
-            | Self::ZoneChange
-            | Self::MapChange
-            | Self::CombatLogVersion
-            | Self::CombatantInfo
-            | Self::Emote
-            | Self::StaggerPrevented
-            | Self::StaggerClear
-            | Self::UnitDied
-            | Self::UnitDestroyed
-            | Self::UnitDissipates
-            | Self::PartyKill
-            | Self::SwingDamageLanded
-            | Self::SwingMissed
-            | Self::EnvironmentalDamage
-            | Self::EnchantApplied
-            | Self::EnchantRemoved
-            | Self::SpellAbsorbed
-            | Self::SpellAbsorbedSupport
-            | Self::SwingDamage => false,
-            _ => true,
-        }
+                | Self::ArenaMatchEnd
+                | Self::EncounterStart
+                | Self::EncounterEnd
+                | Self::ChallengeModeStart
+                | Self::ChallengeModeEnd
+                | Self::WorldMarkerPlaced
+                | Self::WorldMarkerRemoved
+                | Self::ZoneChange
+                | Self::MapChange
+                | Self::CombatLogVersion
+                | Self::CombatantInfo
+                | Self::Emote
+                | Self::StaggerPrevented
+                | Self::StaggerClear
+                | Self::UnitDied
+                | Self::UnitDestroyed
+                | Self::UnitDissipates
+                | Self::PartyKill
+                | Self::SwingDamageLanded
+                | Self::SwingMissed
+                | Self::EnvironmentalDamage
+                | Self::EnchantApplied
+                | Self::EnchantRemoved
+                | Self::SpellAbsorbed
+                | Self::SpellAbsorbedSupport
+                | Self::SwingDamage
+        )
     }
 
     pub fn has_advanced_parameters(&self) -> bool {
-        match self {
+        !matches!(
+            self,
             Self::SpellAuraAppliedDose
-            | Self::SpellAuraRemoved
-            | Self::SpellAuraApplied
-            | Self::SpellAuraRemovedDose
-            | Self::SpellAuraRefresh
-            | Self::SpellPeriodicMissed
-            | Self::SpellCastStart
-            | Self::SpellMissed
-            | Self::SpellHealAbsorbed
-            | Self::SwingMissed
-            | Self::SpellExtraAttacks
-            | Self::SpellSummon
-            | Self::UnitDied
-            | Self::PartyKill
-            | Self::SpellCastFailed
-            | Self::SpellInterrupt
-            | Self::SpellDispel
-            | Self::SpellDispelFailed
-            | Self::SpellCreate
-            | Self::DamageShield
-            | Self::SpellAuraBrokenSpell
-            | Self::RangeMissed
-            | Self::SpellResurrect
-            | Self::SpellInstakill
-            | Self::EnchantApplied
-            | Self::EnchantRemoved
-            | Self::SpellEmpowerStart
-            | Self::SpellEmpowerEnd
-            | Self::SpellAbsorbedSupport
-            | Self::SpellStolen
-            | Self::SpellAuraBroken
-            | Self::SpellEmpowerInterrupt
-            | Self::SpellAbsorbed => false,
-            _ => true,
-        }
+                | Self::SpellAuraRemoved
+                | Self::SpellAuraApplied
+                | Self::SpellAuraRemovedDose
+                | Self::SpellAuraRefresh
+                | Self::SpellPeriodicMissed
+                | Self::SpellCastStart
+                | Self::SpellMissed
+                | Self::SpellHealAbsorbed
+                | Self::SwingMissed
+                | Self::SpellExtraAttacks
+                | Self::SpellSummon
+                | Self::UnitDied
+                | Self::UnitDestroyed
+                | Self::UnitDissipates
+                | Self::PartyKill
+                | Self::SpellCastFailed
+                | Self::SpellInterrupt
+                | Self::SpellDispel
+                | Self::SpellDispelFailed
+                | Self::SpellCreate
+                | Self::DamageShield
+                | Self::SpellAuraBrokenSpell
+                | Self::RangeMissed
+                | Self::SpellResurrect
+                | Self::SpellInstakill
+                | Self::EnchantApplied
+                | Self::EnchantRemoved
+                | Self::SpellEmpowerStart
+                | Self::SpellEmpowerEnd
+                | Self::SpellAbsorbedSupport
+                | Self::SpellStolen
+                | Self::SpellAuraBroken
+                | Self::SpellEmpowerInterrupt
+                | Self::SpellAbsorbed
+        )
     }
 }
 
@@ -324,8 +346,120 @@ impl std::fmt::Display for EventType {
             Self::ChallengeModeEnd => write!(f, "CHALLENGE_MODE_END"),
             Self::WorldMarkerPlaced => write!(f, "WORLD_MARKER_PLACED"),
             Self::WorldMarkerRemoved => write!(f, "WORLD_MARKER_REMOVED"),
+            // Only the symbol is kept, `SymbolTable::event_name` resolves it
+            Self::Unknown(_) => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+// The shape of the advanced parameter block changes between combat log
+// versions so it is picked from the COMBAT_LOG_VERSION header
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum AdvancedLayout {
+    // Pre-Dragonflight logs, `level` is the unit level for everyone
+    Classic,
+    // Players report their item level in place of the unit level
+    Retail,
+    // Adds support power and a second unidentified field after armor
+    #[default]
+    Extended,
+}
+
+impl AdvancedLayout {
+    pub fn from_version(version: u32) -> Self {
+        match version {
+            0..20 => Self::Classic,
+            20 | 21 => Self::Retail,
+            _ => Self::Extended,
+        }
+    }
+
+    pub fn reports_item_level(&self) -> bool {
+        !matches!(self, Self::Classic)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum BuildFlavour {
+    ClassicEra,
+    // Burning Crusade, Wrath and Mists of Pandaria Classic
+    Classic,
+    CataclysmClassic,
+    #[default]
+    Retail,
+}
+
+impl From<&str> for BuildFlavour {
+    // Only the expansion number of the build is looked at (e.g. `1.15.7`,
+    // `4.4.2`, `11.1.5`)
+    fn from(build: &str) -> Self {
+        match build
+            .split('.')
+            .next()
+            .and_then(|major| major.parse::<u32>().ok())
+        {
+            Some(1) => Self::ClassicEra,
+            Some(4) => Self::CataclysmClassic,
+            Some(2..=5) => Self::Classic,
+            _ => Self::Retail,
+        }
+    }
+}
+
+// Everything from the COMBAT_LOG_VERSION header that changes how the rest of
+// the log is laid out. Logs without a header are assumed to be from a current
+// retail client with advanced logging on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Dialect {
+    pub version: u32,
+    pub flavour: BuildFlavour,
+    pub advanced_log: bool,
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Self {
+            version: 22,
+            flavour: BuildFlavour::Retail,
+            advanced_log: true,
+        }
+    }
+}
+
+impl Dialect {
+    pub fn new(version: u32, build: &str, advanced_log: bool) -> Self {
+        Self {
+            version,
+            flavour: BuildFlavour::from(build),
+            advanced_log,
         }
     }
+
+    // `None` when advanced logging is switched off and combat events carry
+    // no advanced parameter block at all
+    pub fn layout(&self) -> Option<AdvancedLayout> {
+        if !self.advanced_log {
+            return None;
+        }
+
+        let layout = match self.flavour {
+            BuildFlavour::Retail => AdvancedLayout::from_version(self.version),
+            BuildFlavour::ClassicEra | BuildFlavour::Classic | BuildFlavour::CataclysmClassic => {
+                AdvancedLayout::Classic
+            }
+        };
+
+        Some(layout)
+    }
+
+    // The unmitigated amount in damage and heal suffixes is only written by
+    // Cataclysm Classic and retail clients
+    pub fn has_base_amount(&self) -> bool {
+        matches!(
+            self.flavour,
+            BuildFlavour::CataclysmClassic | BuildFlavour::Retail
+        )
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -340,6 +474,7 @@ pub enum MissType {
     Parry,
     Reflect,
     Resist,
+    Unknown(Symbol),
 }
 
 impl TryFrom<&str> for MissType {
@@ -366,6 +501,7 @@ impl TryFrom<&str> for MissType {
 pub enum AuraType {
     Buff,
     Debuff,
+    Unknown(Symbol),
 }
 
 impl TryFrom<&str> for AuraType {
@@ -406,6 +542,7 @@ pub enum EnvironmentalType {
     Fire,
     Lava,
     Slime,
+    Unknown(Symbol),
 }
 
 impl TryFrom<&str> for EnvironmentalType {
@@ -457,167 +594,183 @@ pub enum PowerType {
     Happiness,
     ShadowOrbs,
     RuneChromatic,
+    Unknown(u8),
 }
 
-impl TryFrom<u8> for PowerType {
-    type Error = Report;
-
-    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+impl From<u8> for PowerType {
+    fn from(value: u8) -> Self {
         match value {
-            0 => Ok(Self::Mana),
-            1 => Ok(Self::Rage),
-            2 => Ok(Self::Focus),
-            3 => Ok(Self::Energy),
-            4 => Ok(Self::ComboPoints),
-            5 => Ok(Self::Runes),
-            6 => Ok(Self::RunicPower),
-            7 => Ok(Self::SoulShards),
-            8 => Ok(Self::LunarPower),
-            9 => Ok(Self::HolyPower),
-            10 => Ok(Self::Alternate),
-            11 => Ok(Self::Maelstrom),
-            12 => Ok(Self::Chi),
-            13 => Ok(Self::Insanity),
-            14 => Ok(Self::BurningEmbers),
-            15 => Ok(Self::DemonicFury),
-            16 => Ok(Self::ArcaneCharges),
-            17 => Ok(Self::Fury),
-            18 => Ok(Self::Pain),
-            19 => Ok(Self::Essence),
-            20 => Ok(Self::RuneBlood),
-            21 => Ok(Self::RuneFrost),
-            22 => Ok(Self::RuneUnholy),
-            23 => Ok(Self::AlternateQuest),
-            24 => Ok(Self::AlternateEncounte),
-            25 => Ok(Self::AlternateMount),
-            26 => Ok(Self::Balance),
-            27 => Ok(Self::Happiness),
-            28 => Ok(Self::ShadowOrbs),
-            29 => Ok(Self::RuneChromatic),
-            _ => Err(eyre!("invalid value for power type - {value}")),
+            0 => Self::Mana,
+            1 => Self::Rage,
+            2 => Self::Focus,
+            3 => Self::Energy,
+            4 => Self::ComboPoints,
+            5 => Self::Runes,
+            6 => Self::RunicPower,
+            7 => Self::SoulShards,
+            8 => Self::LunarPower,
+            9 => Self::HolyPower,
+            10 => Self::Alternate,
+            11 => Self::Maelstrom,
+            12 => Self::Chi,
+            13 => Self::Insanity,
+            14 => Self::BurningEmbers,
+            15 => Self::DemonicFury,
+            16 => Self::ArcaneCharges,
+            17 => Self::Fury,
+            18 => Self::Pain,
+            19 => Self::Essence,
+            20 => Self::RuneBlood,
+            21 => Self::RuneFrost,
+            22 => Self::RuneUnholy,
+            23 => Self::AlternateQuest,
+            24 => Self::AlternateEncounte,
+            25 => Self::AlternateMount,
+            26 => Self::Balance,
+            27 => Self::Happiness,
+            28 => Self::ShadowOrbs,
+            29 => Self::RuneChromatic,
+            other => Self::Unknown(other),
         }
     }
 }
 
-#[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum SpellSchool {
-    Physical = 1,
-    Holy = 2,
-    HolyStrike = 3,
-    Fire = 4,
-    Flamestrike = 5,
-    Radiant = 6,
-    Nature = 8,
-    Stormstrike = 9,
-    Holystorm = 10,
-    Volcanic = 12,
-    Frost = 16,
-    Froststrike = 17,
-    Holyfrost = 18,
-    Frostfire = 20,
-    Froststorm = 24,
-    Elemental = 28,
-    Shadow = 32,
-    Shadowstrike = 33,
-    Twilight = 34,
-    Shadowflame = 36,
-    Plague = 40,
-    Shadowfrost = 48,
-    Chromatic = 62,
-    Arcane = 64,
-    Spellstrike = 65,
-    Divine = 66,
-    Spellfire = 68,
-    Astral = 72,
-    Spellfrost = 80,
-    Spellshadow = 96,
-    Cosmic = 106,
-    Magic = 126,
-    Chaos = 127,
-}
-
-impl TryFrom<u8> for SpellSchool {
-    type Error = Report;
+// A bitmask of the seven base schools. Multi-school spells set more than one
+// bit and any combination the client sends is kept as is, named or not.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SpellSchool(u8);
+
+impl SpellSchool {
+    pub const PHYSICAL: Self = Self(0x1);
+    pub const HOLY: Self = Self(0x2);
+    pub const FIRE: Self = Self(0x4);
+    pub const NATURE: Self = Self(0x8);
+    pub const FROST: Self = Self(0x10);
+    pub const SHADOW: Self = Self(0x20);
+    pub const ARCANE: Self = Self(0x40);
+
+    const BASE: [(Self, &'static str); 7] = [
+        (Self::PHYSICAL, "Physical"),
+        (Self::HOLY, "Holy"),
+        (Self::FIRE, "Fire"),
+        (Self::NATURE, "Nature"),
+        (Self::FROST, "Frost"),
+        (Self::SHADOW, "Shadow"),
+        (Self::ARCANE, "Arcane"),
+    ];
+
+    const COMBINATIONS: [(u8, &'static str); 28] = [
+        (3, "HolyStrike"),
+        (5, "Flamestrike"),
+        (6, "Radiant"),
+        (9, "Stormstrike"),
+        (10, "Holystorm"),
+        (12, "Volcanic"),
+        (17, "Froststrike"),
+        (18, "Holyfrost"),
+        (20, "Frostfire"),
+        (24, "Froststorm"),
+        (28, "Elemental"),
+        (33, "Shadowstrike"),
+        (34, "Twilight"),
+        (36, "Shadowflame"),
+        (40, "Plague"),
+        (48, "Shadowfrost"),
+        (62, "Chromatic"),
+        (65, "Spellstrike"),
+        (66, "Divine"),
+        (68, "Spellfire"),
+        (72, "Astral"),
+        (80, "Spellfrost"),
+        (96, "Spellshadow"),
+        (106, "Cosmic"),
+        (110, "Cosmic"),
+        (124, "Chaos"),
+        (126, "Magic"),
+        (127, "Chaos"),
+    ];
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
 
-    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
-        match value {
-            1 => Ok(Self::Physical),
-            2 => Ok(Self::Holy),
-            3 => Ok(Self::HolyStrike),
-            4 => Ok(Self::Fire),
-            5 => Ok(Self::Flamestrike),
-            6 => Ok(Self::Radiant),
-            8 => Ok(Self::Nature),
-            9 => Ok(Self::Stormstrike),
-            10 => Ok(Self::Holystorm),
-            12 => Ok(Self::Volcanic),
-            16 => Ok(Self::Frost),
-            17 => Ok(Self::Froststrike),
-            18 => Ok(Self::Holyfrost),
-            20 => Ok(Self::Frostfire),
-            24 => Ok(Self::Froststorm),
-            28 => Ok(Self::Elemental),
-            32 => Ok(Self::Shadow),
-            33 => Ok(Self::Shadowstrike),
-            34 => Ok(Self::Twilight),
-            36 => Ok(Self::Shadowflame),
-            40 => Ok(Self::Plague),
-            48 => Ok(Self::Shadowfrost),
-            62 => Ok(Self::Chromatic),
-            64 => Ok(Self::Arcane),
-            65 => Ok(Self::Spellstrike),
-            66 => Ok(Self::Divine),
-            68 => Ok(Self::Spellfire),
-            72 => Ok(Self::Astral),
-            80 => Ok(Self::Spellfrost),
-            96 => Ok(Self::Spellshadow),
-            106 | 110 => Ok(Self::Cosmic),
-            126 => Ok(Self::Magic),
-            124 | 127 => Ok(Self::Chaos),
-            _ => Ok(Self::Physical),
-            // _ => Err(eyre!("invalid spell school id - {value}")),
-        }
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn intersects(&self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub const fn is_multi_school(&self) -> bool {
+        self.0.count_ones() > 1
+    }
+
+    // The base schools making up this one, lowest bit first
+    pub fn schools(&self) -> impl Iterator<Item = SpellSchool> + use<> {
+        let school = *self;
+        Self::BASE
+            .into_iter()
+            .map(|(base, _)| base)
+            .filter(move |base| school.contains(*base))
+    }
+
+    pub fn name(&self) -> Option<&'static str> {
+        Self::BASE
+            .iter()
+            .map(|(school, name)| (school.0, *name))
+            .chain(Self::COMBINATIONS)
+            .find(|(bits, _)| *bits == self.0)
+            .map(|(_, name)| name)
+    }
+}
+
+impl From<u8> for SpellSchool {
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+
+impl std::ops::BitOr for SpellSchool {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitAnd for SpellSchool {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
     }
 }
 
 impl std::fmt::Display for SpellSchool {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Physical => write!(f, "Physical"),
-            Self::Holy => write!(f, "Holy"),
-            Self::HolyStrike => write!(f, "HolyStrike"),
-            Self::Fire => write!(f, "Fire"),
-            Self::Flamestrike => write!(f, "Flamestrike"),
-            Self::Radiant => write!(f, "Radiant"),
-            Self::Nature => write!(f, "Nature"),
-            Self::Stormstrike => write!(f, "Stormstrike"),
-            Self::Holystorm => write!(f, "Holystorm"),
-            Self::Volcanic => write!(f, "Volcanic"),
-            Self::Frost => write!(f, "Frost"),
-            Self::Froststrike => write!(f, "Froststrike"),
-            Self::Holyfrost => write!(f, "Holyfrost"),
-            Self::Frostfire => write!(f, "Frostfire"),
-            Self::Froststorm => write!(f, "Froststorm"),
-            Self::Elemental => write!(f, "Elemental"),
-            Self::Shadow => write!(f, "Shadow"),
-            Self::Shadowstrike => write!(f, "Shadowstrike"),
-            Self::Twilight => write!(f, "Twilight"),
-            Self::Shadowflame => write!(f, "Shadowflame"),
-            Self::Plague => write!(f, "Plague"),
-            Self::Shadowfrost => write!(f, "Shadowfrost"),
-            Self::Chromatic => write!(f, "Chromatic"),
-            Self::Arcane => write!(f, "Arcane"),
-            Self::Spellstrike => write!(f, "Spellstrike"),
-            Self::Divine => write!(f, "Divine"),
-            Self::Spellfire => write!(f, "Spellfire"),
-            Self::Astral => write!(f, "Astral"),
-            Self::Spellfrost => write!(f, "Spellfrost"),
-            Self::Spellshadow => write!(f, "Spellshadow"),
-            Self::Cosmic => write!(f, "Cosmic"),
-            Self::Magic => write!(f, "Magic"),
-            Self::Chaos => write!(f, "Chaos"),
+        if let Some(name) = self.name() {
+            return write!(f, "{name}");
         }
+
+        // Unnamed combinations are spelled out, with any bits outside the base
+        // schools kept in hex so nothing is lost
+        let mut parts = Self::BASE
+            .iter()
+            .filter(|(school, _)| self.contains(*school))
+            .map(|(_, name)| name.to_string())
+            .collect::<Vec<_>>();
+
+        let unknown = self.0 & 0x80;
+        if unknown != 0 || parts.is_empty() {
+            parts.push(format!("{unknown:#x}"));
+        }
+
+        write!(f, "{}", parts.join("/"))
     }
 }
 
@@ -633,24 +786,23 @@ pub enum RaidFlag {
     Square = 32,
     Cross = 64,
     Skull = 128,
+    Unknown(u32),
 }
 
-impl TryFrom<u32> for RaidFlag {
-    type Error = Report;
-
-    fn try_from(value: u32) -> Result<Self, Self::Error> {
+impl From<u32> for RaidFlag {
+    fn from(value: u32) -> Self {
         match value {
-            0 => Ok(Self::None),
-            1 => Ok(Self::Star),
-            2 => Ok(Self::Circle),
-            4 => Ok(Self::Diamond),
-            8 => Ok(Self::Triangle),
-            16 => Ok(Self::Moon),
-            32 => Ok(Self::Square),
-            64 => Ok(Self::Cross),
-            128 => Ok(Self::Skull),
-            0x80000000 => Ok(Self::None),
-            _ => Err(eyre!("invalid raid flag value: {value}")),
+            0 => Self::None,
+            1 => Self::Star,
+            2 => Self::Circle,
+            4 => Self::Diamond,
+            8 => Self::Triangle,
+            16 => Self::Moon,
+            32 => Self::Square,
+            64 => Self::Cross,
+            128 => Self::Skull,
+            0x80000000 => Self::None,
+            other => Self::Unknown(other),
         }
     }
 }
@@ -667,6 +819,7 @@ impl std::fmt::Display for RaidFlag {
             Self::Square => write!(f, "Square"),
             Self::Cross => write!(f, "Cross"),
             Self::Skull => write!(f, "Skull"),
+            Self::Unknown(value) => write!(f, "Unknown ({value})"),
         }
     }
 }
@@ -690,6 +843,29 @@ impl UnitFlags {
             special: Special::try_from(flag & 0xFFFF0000)?,
         })
     }
+
+    pub fn is_player(&self) -> bool {
+        self.classification == Classification::Player
+    }
+
+    pub fn is_npc(&self) -> bool {
+        self.classification == Classification::Npc
+    }
+
+    pub fn is_pet(&self) -> bool {
+        matches!(
+            self.classification,
+            Classification::Pet | Classification::Guardian
+        )
+    }
+
+    // In the recording player's party or raid, themselves included
+    pub fn is_grouped(&self) -> bool {
+        matches!(
+            self.affiliation,
+            Affiliation::Mine | Affiliation::Party | Affiliation::Raid
+        )
+    }
 }
 
 impl std::fmt::Display for UnitFlags {
@@ -962,6 +1138,215 @@ impl From<u16> for Difficulty {
     }
 }
 
+// Equipment in COMBATANT_INFO is written in this order, one entry per slot
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EquipmentSlot {
+    Head,
+    Neck,
+    Shoulders,
+    Shirt,
+    Chest,
+    Waist,
+    Legs,
+    Feet,
+    Wrist,
+    Hands,
+    Finger1,
+    Finger2,
+    Trinket1,
+    Trinket2,
+    Back,
+    MainHand,
+    OffHand,
+    Tabard,
+}
+
+impl EquipmentSlot {
+    pub const ALL: [Self; 18] = [
+        Self::Head,
+        Self::Neck,
+        Self::Shoulders,
+        Self::Shirt,
+        Self::Chest,
+        Self::Waist,
+        Self::Legs,
+        Self::Feet,
+        Self::Wrist,
+        Self::Hands,
+        Self::Finger1,
+        Self::Finger2,
+        Self::Trinket1,
+        Self::Trinket2,
+        Self::Back,
+        Self::MainHand,
+        Self::OffHand,
+        Self::Tabard,
+    ];
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn is_weapon(&self) -> bool {
+        matches!(self, Self::MainHand | Self::OffHand)
+    }
+
+    pub fn is_trinket(&self) -> bool {
+        matches!(self, Self::Trinket1 | Self::Trinket2)
+    }
+
+    // Worn for looks only and left out of the average item level
+    pub fn is_cosmetic(&self) -> bool {
+        matches!(self, Self::Shirt | Self::Tabard)
+    }
+}
+
+impl TryFrom<usize> for EquipmentSlot {
+    type Error = Report;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Head),
+            1 => Ok(Self::Neck),
+            2 => Ok(Self::Shoulders),
+            3 => Ok(Self::Shirt),
+            4 => Ok(Self::Chest),
+            5 => Ok(Self::Waist),
+            6 => Ok(Self::Legs),
+            7 => Ok(Self::Feet),
+            8 => Ok(Self::Wrist),
+            9 => Ok(Self::Hands),
+            10 => Ok(Self::Finger1),
+            11 => Ok(Self::Finger2),
+            12 => Ok(Self::Trinket1),
+            13 => Ok(Self::Trinket2),
+            14 => Ok(Self::Back),
+            15 => Ok(Self::MainHand),
+            16 => Ok(Self::OffHand),
+            17 => Ok(Self::Tabard),
+            _ => Err(eyre!("invalid equipment slot - {value}")),
+        }
+    }
+}
+
+impl std::fmt::Display for EquipmentSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Head => write!(f, "Head"),
+            Self::Neck => write!(f, "Neck"),
+            Self::Shoulders => write!(f, "Shoulders"),
+            Self::Shirt => write!(f, "Shirt"),
+            Self::Chest => write!(f, "Chest"),
+            Self::Waist => write!(f, "Waist"),
+            Self::Legs => write!(f, "Legs"),
+            Self::Feet => write!(f, "Feet"),
+            Self::Wrist => write!(f, "Wrist"),
+            Self::Hands => write!(f, "Hands"),
+            Self::Finger1 => write!(f, "Finger 1"),
+            Self::Finger2 => write!(f, "Finger 2"),
+            Self::Trinket1 => write!(f, "Trinket 1"),
+            Self::Trinket2 => write!(f, "Trinket 2"),
+            Self::Back => write!(f, "Back"),
+            Self::MainHand => write!(f, "Main Hand"),
+            Self::OffHand => write!(f, "Off Hand"),
+            Self::Tabard => write!(f, "Tabard"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Class {
+    DeathKnight,
+    DemonHunter,
+    Druid,
+    Evoker,
+    Hunter,
+    Mage,
+    Monk,
+    Paladin,
+    Priest,
+    Rogue,
+    Shaman,
+    Warlock,
+    Warrior,
+}
+
+impl Class {
+    // The in-game class colour as 0xRRGGBB
+    pub fn colour(&self) -> u32 {
+        match self {
+            Self::DeathKnight => 0xC41E3A,
+            Self::DemonHunter => 0xA330C9,
+            Self::Druid => 0xFF7C0A,
+            Self::Evoker => 0x33937F,
+            Self::Hunter => 0xAAD372,
+            Self::Mage => 0x3FC7EB,
+            Self::Monk => 0x00FF98,
+            Self::Paladin => 0xF48CBA,
+            Self::Priest => 0xFFFFFF,
+            Self::Rogue => 0xFFF468,
+            Self::Shaman => 0x0070DD,
+            Self::Warlock => 0x8788EE,
+            Self::Warrior => 0xC69B6D,
+        }
+    }
+}
+
+impl std::fmt::Display for Class {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DeathKnight => write!(f, "Death Knight"),
+            Self::DemonHunter => write!(f, "Demon Hunter"),
+            Self::Druid => write!(f, "Druid"),
+            Self::Evoker => write!(f, "Evoker"),
+            Self::Hunter => write!(f, "Hunter"),
+            Self::Mage => write!(f, "Mage"),
+            Self::Monk => write!(f, "Monk"),
+            Self::Paladin => write!(f, "Paladin"),
+            Self::Priest => write!(f, "Priest"),
+            Self::Rogue => write!(f, "Rogue"),
+            Self::Shaman => write!(f, "Shaman"),
+            Self::Warlock => write!(f, "Warlock"),
+            Self::Warrior => write!(f, "Warrior"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Role {
+    Tank,
+    Healer,
+    Damage,
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tank => write!(f, "Tank"),
+            Self::Healer => write!(f, "Healer"),
+            Self::Damage => write!(f, "Damage"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PrimaryStat {
+    Strength,
+    Agility,
+    Intelligence,
+}
+
+impl std::fmt::Display for PrimaryStat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Strength => write!(f, "Strength"),
+            Self::Agility => write!(f, "Agility"),
+            Self::Intelligence => write!(f, "Intelligence"),
+        }
+    }
+}
+
 #[repr(u16)]
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Specialization {
@@ -1030,67 +1415,68 @@ pub enum Specialization {
     FuryWarrior = 72,
     ProtectionWarrior = 73,
     WarriorInitial = 1446,
-}
 
-impl TryFrom<u16> for Specialization {
-    type Error = Report;
+    // Never a real spec id
+    Unknown(u16) = 0,
+}
 
-    fn try_from(value: u16) -> Result<Self, Self::Error> {
+impl From<u16> for Specialization {
+    fn from(value: u16) -> Self {
         match value {
-            250 => Ok(Self::BloodDeathKnight),
-            251 => Ok(Self::FrostDeathKnight),
-            252 => Ok(Self::UnholyDeathKnight),
-            1455 => Ok(Self::DeathKnightInitial),
-            577 => Ok(Self::HavocDemonHunter),
-            581 => Ok(Self::VengeanceDemonHunter),
-            1480 => Ok(Self::DevourerDemonHunter),
-            1456 => Ok(Self::DemonHunterInitial),
-            102 => Ok(Self::BalanceDruid),
-            103 => Ok(Self::FeralDruid),
-            104 => Ok(Self::GuardianDruid),
-            105 => Ok(Self::RestorationDruid),
-            1447 => Ok(Self::DruidInitial),
-            1467 => Ok(Self::DevastationEvoker),
-            1468 => Ok(Self::PreservationEvoker),
-            1473 => Ok(Self::AugmentationEvoker),
-            1465 => Ok(Self::EvokerInitial),
-            253 => Ok(Self::BeastMasteryHunter),
-            254 => Ok(Self::MarksmanshipHunter),
-            255 => Ok(Self::SurvivalHunter),
-            1448 => Ok(Self::HunterInitial),
-            62 => Ok(Self::ArcaneMage),
-            63 => Ok(Self::FireMage),
-            64 => Ok(Self::FrostMage),
-            1449 => Ok(Self::MageInitial),
-            268 => Ok(Self::BrewmasterMonk),
-            269 => Ok(Self::WindwalkerMonk),
-            270 => Ok(Self::MistweaverMonk),
-            1450 => Ok(Self::MonkInitial),
-            65 => Ok(Self::HolyPaladin),
-            66 => Ok(Self::ProtectionPaladin),
-            70 => Ok(Self::RetributionPaladin),
-            1451 => Ok(Self::PaladinInitial),
-            256 => Ok(Self::DisciplinePriest),
-            257 => Ok(Self::HolyPriest),
-            258 => Ok(Self::ShadowPriest),
-            1452 => Ok(Self::PriestInitial),
-            259 => Ok(Self::AssassinationRogue),
-            260 => Ok(Self::OutlawRogue),
-            261 => Ok(Self::SubtletyRogue),
-            1453 => Ok(Self::RogueInitial),
-            262 => Ok(Self::ElementalShaman),
-            263 => Ok(Self::EnhancementShaman),
-            264 => Ok(Self::RestorationShaman),
-            1444 => Ok(Self::ShamanInitial),
-            265 => Ok(Self::AfflictionWarlock),
-            266 => Ok(Self::DemonologyWarlock),
-            267 => Ok(Self::DestructionWarlock),
-            1454 => Ok(Self::WarlockInitial),
-            71 => Ok(Self::ArmsWarrior),
-            72 => Ok(Self::FuryWarrior),
-            73 => Ok(Self::ProtectionWarrior),
-            1446 => Ok(Self::WarriorInitial),
-            other => Err(eyre!("unknown specialization id: {other}")),
+            250 => Self::BloodDeathKnight,
+            251 => Self::FrostDeathKnight,
+            252 => Self::UnholyDeathKnight,
+            1455 => Self::DeathKnightInitial,
+            577 => Self::HavocDemonHunter,
+            581 => Self::VengeanceDemonHunter,
+            1480 => Self::DevourerDemonHunter,
+            1456 => Self::DemonHunterInitial,
+            102 => Self::BalanceDruid,
+            103 => Self::FeralDruid,
+            104 => Self::GuardianDruid,
+            105 => Self::RestorationDruid,
+            1447 => Self::DruidInitial,
+            1467 => Self::DevastationEvoker,
+            1468 => Self::PreservationEvoker,
+            1473 => Self::AugmentationEvoker,
+            1465 => Self::EvokerInitial,
+            253 => Self::BeastMasteryHunter,
+            254 => Self::MarksmanshipHunter,
+            255 => Self::SurvivalHunter,
+            1448 => Self::HunterInitial,
+            62 => Self::ArcaneMage,
+            63 => Self::FireMage,
+            64 => Self::FrostMage,
+            1449 => Self::MageInitial,
+            268 => Self::BrewmasterMonk,
+            269 => Self::WindwalkerMonk,
+            270 => Self::MistweaverMonk,
+            1450 => Self::MonkInitial,
+            65 => Self::HolyPaladin,
+            66 => Self::ProtectionPaladin,
+            70 => Self::RetributionPaladin,
+            1451 => Self::PaladinInitial,
+            256 => Self::DisciplinePriest,
+            257 => Self::HolyPriest,
+            258 => Self::ShadowPriest,
+            1452 => Self::PriestInitial,
+            259 => Self::AssassinationRogue,
+            260 => Self::OutlawRogue,
+            261 => Self::SubtletyRogue,
+            1453 => Self::RogueInitial,
+            262 => Self::ElementalShaman,
+            263 => Self::EnhancementShaman,
+            264 => Self::RestorationShaman,
+            1444 => Self::ShamanInitial,
+            265 => Self::AfflictionWarlock,
+            266 => Self::DemonologyWarlock,
+            267 => Self::DestructionWarlock,
+            1454 => Self::WarlockInitial,
+            71 => Self::ArmsWarrior,
+            72 => Self::FuryWarrior,
+            73 => Self::ProtectionWarrior,
+            1446 => Self::WarriorInitial,
+            other => Self::Unknown(other),
         }
     }
 }
@@ -1163,11 +1549,252 @@ impl std::fmt::Display for Specialization {
             Self::FuryWarrior => write!(f, "Fury Warrior"),
             Self::ProtectionWarrior => write!(f, "Protection Warrior"),
             Self::WarriorInitial => write!(f, "Warrior (Initial)"),
+
+            Self::Unknown(value) => write!(f, "Unknown (ID: {value})"),
+        }
+    }
+}
+
+impl Specialization {
+    pub fn id(&self) -> u16 {
+        match self {
+            Self::BloodDeathKnight => 250,
+            Self::FrostDeathKnight => 251,
+            Self::UnholyDeathKnight => 252,
+            Self::DeathKnightInitial => 1455,
+            Self::HavocDemonHunter => 577,
+            Self::VengeanceDemonHunter => 581,
+            Self::DevourerDemonHunter => 1480,
+            Self::DemonHunterInitial => 1456,
+            Self::BalanceDruid => 102,
+            Self::FeralDruid => 103,
+            Self::GuardianDruid => 104,
+            Self::RestorationDruid => 105,
+            Self::DruidInitial => 1447,
+            Self::DevastationEvoker => 1467,
+            Self::PreservationEvoker => 1468,
+            Self::AugmentationEvoker => 1473,
+            Self::EvokerInitial => 1465,
+            Self::BeastMasteryHunter => 253,
+            Self::MarksmanshipHunter => 254,
+            Self::SurvivalHunter => 255,
+            Self::HunterInitial => 1448,
+            Self::ArcaneMage => 62,
+            Self::FireMage => 63,
+            Self::FrostMage => 64,
+            Self::MageInitial => 1449,
+            Self::BrewmasterMonk => 268,
+            Self::WindwalkerMonk => 269,
+            Self::MistweaverMonk => 270,
+            Self::MonkInitial => 1450,
+            Self::HolyPaladin => 65,
+            Self::ProtectionPaladin => 66,
+            Self::RetributionPaladin => 70,
+            Self::PaladinInitial => 1451,
+            Self::DisciplinePriest => 256,
+            Self::HolyPriest => 257,
+            Self::ShadowPriest => 258,
+            Self::PriestInitial => 1452,
+            Self::AssassinationRogue => 259,
+            Self::OutlawRogue => 260,
+            Self::SubtletyRogue => 261,
+            Self::RogueInitial => 1453,
+            Self::ElementalShaman => 262,
+            Self::EnhancementShaman => 263,
+            Self::RestorationShaman => 264,
+            Self::ShamanInitial => 1444,
+            Self::AfflictionWarlock => 265,
+            Self::DemonologyWarlock => 266,
+            Self::DestructionWarlock => 267,
+            Self::WarlockInitial => 1454,
+            Self::ArmsWarrior => 71,
+            Self::FuryWarrior => 72,
+            Self::ProtectionWarrior => 73,
+            Self::WarriorInitial => 1446,
+            Self::Unknown(id) => *id,
+        }
+    }
+
+    pub fn class(&self) -> Option<Class> {
+        match self {
+            Self::BloodDeathKnight
+            | Self::FrostDeathKnight
+            | Self::UnholyDeathKnight
+            | Self::DeathKnightInitial => Some(Class::DeathKnight),
+            Self::HavocDemonHunter
+            | Self::VengeanceDemonHunter
+            | Self::DevourerDemonHunter
+            | Self::DemonHunterInitial => Some(Class::DemonHunter),
+            Self::BalanceDruid
+            | Self::FeralDruid
+            | Self::GuardianDruid
+            | Self::RestorationDruid
+            | Self::DruidInitial => Some(Class::Druid),
+            Self::DevastationEvoker
+            | Self::PreservationEvoker
+            | Self::AugmentationEvoker
+            | Self::EvokerInitial => Some(Class::Evoker),
+            Self::BeastMasteryHunter
+            | Self::MarksmanshipHunter
+            | Self::SurvivalHunter
+            | Self::HunterInitial => Some(Class::Hunter),
+            Self::ArcaneMage | Self::FireMage | Self::FrostMage | Self::MageInitial => {
+                Some(Class::Mage)
+            }
+            Self::BrewmasterMonk
+            | Self::WindwalkerMonk
+            | Self::MistweaverMonk
+            | Self::MonkInitial => Some(Class::Monk),
+            Self::HolyPaladin
+            | Self::ProtectionPaladin
+            | Self::RetributionPaladin
+            | Self::PaladinInitial => Some(Class::Paladin),
+            Self::DisciplinePriest
+            | Self::HolyPriest
+            | Self::ShadowPriest
+            | Self::PriestInitial => Some(Class::Priest),
+            Self::AssassinationRogue
+            | Self::OutlawRogue
+            | Self::SubtletyRogue
+            | Self::RogueInitial => Some(Class::Rogue),
+            Self::ElementalShaman
+            | Self::EnhancementShaman
+            | Self::RestorationShaman
+            | Self::ShamanInitial => Some(Class::Shaman),
+            Self::AfflictionWarlock
+            | Self::DemonologyWarlock
+            | Self::DestructionWarlock
+            | Self::WarlockInitial => Some(Class::Warlock),
+            Self::ArmsWarrior
+            | Self::FuryWarrior
+            | Self::ProtectionWarrior
+            | Self::WarriorInitial => Some(Class::Warrior),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    // Fights in melee range. Healers count as ranged, whatever their class.
+    pub fn is_melee(&self) -> bool {
+        matches!(
+            self,
+            Self::BloodDeathKnight
+                | Self::FrostDeathKnight
+                | Self::UnholyDeathKnight
+                | Self::DeathKnightInitial
+                | Self::HavocDemonHunter
+                | Self::VengeanceDemonHunter
+                | Self::DemonHunterInitial
+                | Self::FeralDruid
+                | Self::GuardianDruid
+                | Self::SurvivalHunter
+                | Self::BrewmasterMonk
+                | Self::WindwalkerMonk
+                | Self::MonkInitial
+                | Self::ProtectionPaladin
+                | Self::RetributionPaladin
+                | Self::PaladinInitial
+                | Self::AssassinationRogue
+                | Self::OutlawRogue
+                | Self::SubtletyRogue
+                | Self::RogueInitial
+                | Self::EnhancementShaman
+                | Self::ArmsWarrior
+                | Self::FuryWarrior
+                | Self::ProtectionWarrior
+                | Self::WarriorInitial
+        )
+    }
+
+    pub fn is_ranged(&self) -> bool {
+        !self.is_melee() && !matches!(self, Self::Unknown(_))
+    }
+
+    // Initial specs are treated as damage dealers
+    pub fn role(&self) -> Option<Role> {
+        match self {
+            Self::BloodDeathKnight
+            | Self::VengeanceDemonHunter
+            | Self::GuardianDruid
+            | Self::BrewmasterMonk
+            | Self::ProtectionPaladin
+            | Self::ProtectionWarrior => Some(Role::Tank),
+            Self::RestorationDruid
+            | Self::PreservationEvoker
+            | Self::MistweaverMonk
+            | Self::HolyPaladin
+            | Self::DisciplinePriest
+            | Self::HolyPriest
+            | Self::RestorationShaman => Some(Role::Healer),
+            Self::Unknown(_) => None,
+            _ => Some(Role::Damage),
+        }
+    }
+
+    // Initial specs use whatever their class levels with before choosing one
+    pub fn primary_stat(&self) -> Option<PrimaryStat> {
+        match self {
+            Self::BloodDeathKnight
+            | Self::FrostDeathKnight
+            | Self::UnholyDeathKnight
+            | Self::DeathKnightInitial
+            | Self::ProtectionPaladin
+            | Self::RetributionPaladin
+            | Self::PaladinInitial
+            | Self::ArmsWarrior
+            | Self::FuryWarrior
+            | Self::ProtectionWarrior
+            | Self::WarriorInitial => Some(PrimaryStat::Strength),
+
+            Self::HavocDemonHunter
+            | Self::VengeanceDemonHunter
+            | Self::DemonHunterInitial
+            | Self::FeralDruid
+            | Self::GuardianDruid
+            | Self::BeastMasteryHunter
+            | Self::MarksmanshipHunter
+            | Self::SurvivalHunter
+            | Self::HunterInitial
+            | Self::BrewmasterMonk
+            | Self::WindwalkerMonk
+            | Self::MonkInitial
+            | Self::AssassinationRogue
+            | Self::OutlawRogue
+            | Self::SubtletyRogue
+            | Self::RogueInitial
+            | Self::EnhancementShaman => Some(PrimaryStat::Agility),
+
+            Self::DevourerDemonHunter
+            | Self::BalanceDruid
+            | Self::RestorationDruid
+            | Self::DruidInitial
+            | Self::DevastationEvoker
+            | Self::PreservationEvoker
+            | Self::AugmentationEvoker
+            | Self::EvokerInitial
+            | Self::ArcaneMage
+            | Self::FireMage
+            | Self::FrostMage
+            | Self::MageInitial
+            | Self::MistweaverMonk
+            | Self::HolyPaladin
+            | Self::DisciplinePriest
+            | Self::HolyPriest
+            | Self::ShadowPriest
+            | Self::PriestInitial
+            | Self::ElementalShaman
+            | Self::RestorationShaman
+            | Self::ShamanInitial
+            | Self::AfflictionWarlock
+            | Self::DemonologyWarlock
+            | Self::DestructionWarlock
+            | Self::WarlockInitial => Some(PrimaryStat::Intelligence),
+
+            Self::Unknown(_) => None,
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct MultiValue<T>(pub Vec<T>);
 
 impl<T> std::ops::Deref for MultiValue<T> {
@@ -1183,7 +1810,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.0.len() {
-            0 => return Ok(()),
+            0 => Ok(()),
             1 => write!(f, "{:?}", self.0[0]),
             len => {
                 let last = len - 1;
@@ -1200,3 +1827,27 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod types_tests {
+    use super::*;
+
+    #[test]
+    fn specs_know_their_class_and_range() {
+        let spec = Specialization::from(1473);
+        assert_eq!(spec.class(), Some(Class::Evoker));
+        assert_eq!(spec.role(), Some(Role::Damage));
+        assert_eq!(spec.primary_stat(), Some(PrimaryStat::Intelligence));
+        assert!(spec.is_ranged());
+        assert_eq!(spec.class().map(|c| c.colour()), Some(0x33937F));
+
+        let spec = Specialization::SurvivalHunter;
+        assert!(spec.is_melee());
+        assert!(!spec.is_ranged());
+        assert!(Specialization::HolyPaladin.is_ranged());
+
+        let unknown = Specialization::from(9999);
+        assert!(!unknown.is_melee() && !unknown.is_ranged());
+        assert_eq!(unknown.role(), None);
+    }
+}